@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("HhkRf7yGwgt62YbtsLE7sF7GcnGRLkJ7Zrko4XcKCvpM");
+
+/// Mock Orca Whirlpool/Raydium CLMM-style pool for local tests
+/// Implements the CLMM adapter interface used by the vault program:
+/// open_position, increase_liquidity, decrease_liquidity, update_range, collect_fees
+/// Liquidity and fees are denominated in the pool's single token to keep tests simple
+#[program]
+pub mod mock_clmm {
+    use super::*;
+    
+    /// Create a pool for `mint` with a token vault, starting at `tick_current`
+    pub fn init_pool(ctx: Context<InitPool>, tick_current: i32) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.mint = ctx.accounts.mint.key();
+        pool.token_vault = ctx.accounts.token_vault.key();
+        pool.tick_current = tick_current;
+        pool.authority = ctx.accounts.authority.key();
+        pool.bump = ctx.bumps.pool;
+    
+        Ok(())
+    }
+    
+    /// Move the pool price (test helper standing in for swaps)
+    pub fn set_tick(ctx: Context<SetTick>, tick_current: i32) -> Result<()> {
+        ctx.accounts.pool.tick_current = tick_current;
+        Ok(())
+    }
+    
+    /// Open an empty position over [tick_lower, tick_upper)
+    pub fn open_position(ctx: Context<OpenPosition>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+        require!(tick_lower < tick_upper, ClmmError::InvalidTickRange);
+    
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.pool = ctx.accounts.pool.key();
+        position.tick_lower = tick_lower;
+        position.tick_upper = tick_upper;
+        position.liquidity = 0;
+        position.fees_owed = 0;
+        position.bump = ctx.bumps.position;
+    
+        Ok(())
+    }
+    
+    /// Add liquidity to the position
+    pub fn increase_liquidity(ctx: Context<ModifyLiquidity>, amount: u64) -> Result<()> {
+        require!(amount > 0, ClmmError::InvalidAmount);
+    
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.token_account.to_account_info(),
+            to: ctx.accounts.token_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    
+        let position = &mut ctx.accounts.position;
+        position.liquidity = position.liquidity
+            .checked_add(amount)
+            .ok_or(ClmmError::MathOverflow)?;
+    
+        Ok(())
+    }
+    
+    /// Remove liquidity from the position
+    pub fn decrease_liquidity(ctx: Context<ModifyLiquidity>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.liquidity = position.liquidity
+            .checked_sub(amount)
+            .ok_or(ClmmError::InvalidAmount)?;
+    
+        transfer_from_pool(&ctx, amount)
+    }
+    
+    /// Move an empty position to a new range
+    pub fn update_range(ctx: Context<UpdateRange>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+        require!(tick_lower < tick_upper, ClmmError::InvalidTickRange);
+    
+        let position = &mut ctx.accounts.position;
+        require!(position.liquidity == 0, ClmmError::PositionNotEmpty);
+        position.tick_lower = tick_lower;
+        position.tick_upper = tick_upper;
+    
+        Ok(())
+    }
+    
+    /// Credit trading fees to a position (test helper standing in for swap volume)
+    pub fn accrue_fees(ctx: Context<AccrueFees>, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.token_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    
+        let position = &mut ctx.accounts.position;
+        position.fees_owed = position.fees_owed
+            .checked_add(amount)
+            .ok_or(ClmmError::MathOverflow)?;
+    
+        Ok(())
+    }
+    
+    /// Pay out all fees owed to the position
+    pub fn collect_fees(ctx: Context<ModifyLiquidity>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        let amount = position.fees_owed;
+        position.fees_owed = 0;
+    
+        transfer_from_pool(&ctx, amount)
+    }
+}
+
+fn transfer_from_pool(ctx: &Context<ModifyLiquidity>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let pool = &ctx.accounts.pool;
+    let seeds = &[b"clmm_pool".as_ref(), pool.mint.as_ref(), &[pool.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.token_vault.to_account_info(),
+        to: ctx.accounts.token_account.to_account_info(),
+        authority: ctx.accounts.pool.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Field order up to `tick_current` is part of the adapter interface
+#[account]
+pub struct ClmmPool {
+    pub mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub tick_current: i32,
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl ClmmPool {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 32 + 1;
+}
+
+/// Field order up to `fees_owed` is part of the adapter interface
+#[account]
+pub struct ClmmPosition {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u64,
+    pub fees_owed: u64,
+    pub bump: u8,
+}
+
+impl ClmmPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 4 + 4 + 8 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ClmmPool::LEN,
+        seeds = [b"clmm_pool", mint.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, ClmmPool>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_vault", pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTick<'info> {
+    #[account(mut, has_one = authority)]
+    pub pool: Account<'info, ClmmPool>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    pub pool: Account<'info, ClmmPool>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = ClmmPosition::LEN,
+        seeds = [b"clmm_position", pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, ClmmPosition>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ModifyLiquidity<'info> {
+    pub pool: Account<'info, ClmmPool>,
+    
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = owner
+    )]
+    pub position: Account<'info, ClmmPosition>,
+    
+    #[account(
+        mut,
+        address = pool.token_vault
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRange<'info> {
+    pub pool: Account<'info, ClmmPool>,
+    
+    #[account(
+        mut,
+        has_one = pool,
+        has_one = owner
+    )]
+    pub position: Account<'info, ClmmPosition>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AccrueFees<'info> {
+    pub pool: Account<'info, ClmmPool>,
+    
+    #[account(mut, has_one = pool)]
+    pub position: Account<'info, ClmmPosition>,
+    
+    #[account(
+        mut,
+        address = pool.token_vault
+    )]
+    pub token_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    
+    pub funder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum ClmmError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Invalid tick range")]
+    InvalidTickRange,
+    
+    #[msg("Position still holds liquidity")]
+    PositionNotEmpty,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
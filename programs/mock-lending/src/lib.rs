@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("3xPMVRmGRtBi6PK84yh1EU98MKvGwuTRU9vV9tRQnwB4");
+
+/// Mock Solend/Kamino-Lend-style lending market for local tests
+/// Implements the lending adapter interface used by the vault program:
+/// init_obligation, deposit_collateral, withdraw_collateral, borrow, repay
+#[program]
+pub mod mock_lending {
+    use super::*;
+    
+    /// Create a single-asset lending market with a liquidity supply account
+    pub fn init_market(ctx: Context<InitMarket>, max_ltv_bps: u16) -> Result<()> {
+        require!(max_ltv_bps <= 10000, LendingError::InvalidAmount);
+    
+        let market = &mut ctx.accounts.market;
+        market.authority = ctx.accounts.authority.key();
+        market.mint = ctx.accounts.mint.key();
+        market.liquidity_supply = ctx.accounts.liquidity_supply.key();
+        market.max_ltv_bps = max_ltv_bps;
+        market.bump = ctx.bumps.market;
+    
+        Ok(())
+    }
+    
+    /// Open an obligation for `owner`, keyed by an arbitrary seed account
+    pub fn init_obligation(ctx: Context<InitObligation>) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.owner = ctx.accounts.owner.key();
+        obligation.market = ctx.accounts.market.key();
+        obligation.deposited_amount = 0;
+        obligation.borrowed_amount = 0;
+        obligation.seed = ctx.accounts.seed.key();
+        obligation.bump = ctx.bumps.obligation;
+    
+        Ok(())
+    }
+    
+    /// Deposit collateral into the obligation
+    pub fn deposit_collateral(ctx: Context<LendingAction>, amount: u64) -> Result<()> {
+        transfer_in(&ctx, amount)?;
+    
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.deposited_amount = obligation.deposited_amount
+            .checked_add(amount)
+            .ok_or(LendingError::MathOverflow)?;
+    
+        Ok(())
+    }
+    
+    /// Withdraw collateral, keeping the obligation within max LTV
+    pub fn withdraw_collateral(ctx: Context<LendingAction>, amount: u64) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.deposited_amount = obligation.deposited_amount
+            .checked_sub(amount)
+            .ok_or(LendingError::InvalidAmount)?;
+        check_ltv(obligation, ctx.accounts.market.max_ltv_bps)?;
+    
+        transfer_out(&ctx, amount)
+    }
+    
+    /// Borrow liquidity against deposited collateral
+    pub fn borrow(ctx: Context<LendingAction>, amount: u64) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.borrowed_amount = obligation.borrowed_amount
+            .checked_add(amount)
+            .ok_or(LendingError::MathOverflow)?;
+        check_ltv(obligation, ctx.accounts.market.max_ltv_bps)?;
+    
+        transfer_out(&ctx, amount)
+    }
+    
+    /// Repay borrowed liquidity (capped at the outstanding debt)
+    pub fn repay(ctx: Context<LendingAction>, amount: u64) -> Result<()> {
+        let amount = amount.min(ctx.accounts.obligation.borrowed_amount);
+        transfer_in(&ctx, amount)?;
+    
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.borrowed_amount = obligation.borrowed_amount
+            .checked_sub(amount)
+            .ok_or(LendingError::MathOverflow)?;
+    
+        Ok(())
+    }
+    
+    /// Write down an obligation's collateral without releasing it
+    /// (tests simulate a collateral loss, e.g. a depeg, with this)
+    pub fn write_down_collateral(ctx: Context<WriteDownCollateral>, amount: u64) -> Result<()> {
+        let obligation = &mut ctx.accounts.obligation;
+        obligation.deposited_amount = obligation.deposited_amount
+            .checked_sub(amount)
+            .ok_or(LendingError::InvalidAmount)?;
+    
+        Ok(())
+    }
+}
+
+fn check_ltv(obligation: &Obligation, max_ltv_bps: u16) -> Result<()> {
+    let max_borrow = (obligation.deposited_amount as u128)
+        .checked_mul(max_ltv_bps as u128)
+        .ok_or(LendingError::MathOverflow)?
+        / 10000;
+    require!(
+        (obligation.borrowed_amount as u128) <= max_borrow,
+        LendingError::ExceedsMaxLtv
+    );
+    Ok(())
+}
+
+fn transfer_in(ctx: &Context<LendingAction>, amount: u64) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.liquidity_supply.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, amount)
+}
+
+fn transfer_out(ctx: &Context<LendingAction>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let seeds = &[b"market".as_ref(), market.mint.as_ref(), &[market.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.liquidity_supply.to_account_info(),
+        to: ctx.accounts.user_token_account.to_account_info(),
+        authority: ctx.accounts.market.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token::transfer(cpi_ctx, amount)
+}
+
+#[account]
+pub struct Market {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub liquidity_supply: Pubkey,
+    pub max_ltv_bps: u16,
+    pub bump: u8,
+}
+
+impl Market {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 + 1;
+}
+
+/// Field order up to `borrowed_amount` is part of the adapter interface
+#[account]
+pub struct Obligation {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub deposited_amount: u64,
+    pub borrowed_amount: u64,
+    pub seed: Pubkey,
+    pub bump: u8,
+}
+
+impl Obligation {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 32 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Market::LEN,
+        seeds = [b"market", mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"supply", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = market
+    )]
+    pub liquidity_supply: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitObligation<'info> {
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = Obligation::LEN,
+        seeds = [b"obligation", market.key().as_ref(), owner.key().as_ref(), seed.key().as_ref()],
+        bump
+    )]
+    pub obligation: Account<'info, Obligation>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Arbitrary account distinguishing obligations of the same owner
+    pub seed: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LendingAction<'info> {
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        has_one = market,
+        has_one = owner
+    )]
+    pub obligation: Account<'info, Obligation>,
+    
+    #[account(
+        mut,
+        address = market.liquidity_supply
+    )]
+    pub liquidity_supply: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WriteDownCollateral<'info> {
+    #[account(
+        has_one = authority
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        has_one = market
+    )]
+    pub obligation: Account<'info, Obligation>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[error_code]
+pub enum LendingError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Borrow exceeds max LTV")]
+    ExceedsMaxLtv,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,274 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("FAiNeANoqCtJgXRg2AoZPgKGxeCoCeJ496p63QVdZCfq");
+
+/// Mock Drift/Mango-style perpetual futures market for local tests
+/// Implements the perp adapter interface used by the vault program:
+/// init_position, deposit_margin, withdraw_margin, place_order
+/// Positions are margined in the base asset, so notional = |base_size|.
+/// Orders fill at the market's mark price (1e8 scale), set by the market authority
+#[program]
+pub mod mock_perp {
+    use super::*;
+    
+    /// Create a perp market margined in `mint` with a margin vault
+    pub fn init_market(
+        ctx: Context<InitMarket>,
+        max_leverage_bps: u16,
+        mark_price: u64,
+    ) -> Result<()> {
+        require!(max_leverage_bps >= 10000, PerpError::InvalidAmount);
+        require!(mark_price > 0, PerpError::InvalidAmount);
+    
+        let market = &mut ctx.accounts.market;
+        market.authority = ctx.accounts.authority.key();
+        market.mint = ctx.accounts.mint.key();
+        market.margin_vault = ctx.accounts.margin_vault.key();
+        market.max_leverage_bps = max_leverage_bps;
+        market.mark_price = mark_price;
+        market.bump = ctx.bumps.market;
+    
+        Ok(())
+    }
+    
+    /// Move the market's mark price (tests simulate price moves with this)
+    pub fn set_mark_price(ctx: Context<SetMarkPrice>, mark_price: u64) -> Result<()> {
+        require!(mark_price > 0, PerpError::InvalidAmount);
+        ctx.accounts.market.mark_price = mark_price;
+        Ok(())
+    }
+    
+    /// Open a perp position for `owner`
+    pub fn init_position(ctx: Context<InitPosition>) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.owner = ctx.accounts.owner.key();
+        position.market = ctx.accounts.market.key();
+        position.base_size = 0;
+        position.margin = 0;
+        position.bump = ctx.bumps.position;
+    
+        Ok(())
+    }
+    
+    /// Post margin to the position
+    pub fn deposit_margin(ctx: Context<MarginAction>, amount: u64) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.margin_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    
+        let position = &mut ctx.accounts.position;
+        position.margin = position.margin
+            .checked_add(amount)
+            .ok_or(PerpError::MathOverflow)?;
+    
+        Ok(())
+    }
+    
+    /// Withdraw margin, keeping the position within max leverage
+    pub fn withdraw_margin(ctx: Context<MarginAction>, amount: u64) -> Result<()> {
+        let position = &mut ctx.accounts.position;
+        position.margin = position.margin
+            .checked_sub(amount)
+            .ok_or(PerpError::InvalidAmount)?;
+        check_leverage(position, ctx.accounts.market.max_leverage_bps)?;
+    
+        let market = &ctx.accounts.market;
+        let seeds = &[b"perp_market".as_ref(), market.mint.as_ref(), &[market.bump]];
+        let signer = &[&seeds[..]];
+    
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.margin_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token::transfer(cpi_ctx, amount)
+    }
+    
+    /// Change the position's base size (negative = sell / increase short)
+    /// Sells fail below `limit_price`, buys fail above it
+    pub fn place_order(ctx: Context<PlaceOrder>, base_delta: i64, limit_price: u64) -> Result<()> {
+        require!(base_delta != 0, PerpError::InvalidAmount);
+    
+        let mark_price = ctx.accounts.market.mark_price;
+        if base_delta < 0 {
+            require!(mark_price >= limit_price, PerpError::PriceLimitExceeded);
+        } else {
+            require!(mark_price <= limit_price, PerpError::PriceLimitExceeded);
+        }
+    
+        let position = &mut ctx.accounts.position;
+        position.base_size = position.base_size
+            .checked_add(base_delta)
+            .ok_or(PerpError::MathOverflow)?;
+        check_leverage(position, ctx.accounts.market.max_leverage_bps)?;
+    
+        Ok(())
+    }
+}
+
+fn check_leverage(position: &PerpPosition, max_leverage_bps: u16) -> Result<()> {
+    let max_notional = (position.margin as u128)
+        .checked_mul(max_leverage_bps as u128)
+        .ok_or(PerpError::MathOverflow)?
+        / 10000;
+    require!(
+        (position.base_size.unsigned_abs() as u128) <= max_notional,
+        PerpError::ExceedsMaxLeverage
+    );
+    Ok(())
+}
+
+#[account]
+pub struct PerpMarket {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub margin_vault: Pubkey,
+    pub max_leverage_bps: u16,
+    pub mark_price: u64,
+    pub bump: u8,
+}
+
+impl PerpMarket {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 + 8 + 1;
+}
+
+/// Field order up to `margin` is part of the adapter interface
+#[account]
+pub struct PerpPosition {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub base_size: i64,
+    pub margin: u64,
+    pub bump: u8,
+}
+
+impl PerpPosition {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitMarket<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PerpMarket::LEN,
+        seeds = [b"perp_market", mint.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, PerpMarket>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"margin_vault", market.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = market
+    )]
+    pub margin_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetMarkPrice<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub market: Account<'info, PerpMarket>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitPosition<'info> {
+    pub market: Account<'info, PerpMarket>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = PerpPosition::LEN,
+        seeds = [b"perp_position", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, PerpPosition>,
+    
+    pub owner: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MarginAction<'info> {
+    pub market: Account<'info, PerpMarket>,
+    
+    #[account(
+        mut,
+        has_one = market,
+        has_one = owner
+    )]
+    pub position: Account<'info, PerpPosition>,
+    
+    #[account(
+        mut,
+        address = market.margin_vault
+    )]
+    pub margin_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    pub market: Account<'info, PerpMarket>,
+    
+    #[account(
+        mut,
+        has_one = market,
+        has_one = owner
+    )]
+    pub position: Account<'info, PerpPosition>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[error_code]
+pub enum PerpError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Position exceeds max leverage")]
+    ExceedsMaxLeverage,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+    
+    #[msg("Mark price outside the order's limit")]
+    PriceLimitExceeded,
+}
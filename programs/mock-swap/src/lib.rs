@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+declare_id!("CqHgNxhZK97JTjr7re1NrzL4eHVdeeP8gaRKB9oDw22q");
+
+/// Mock Orca/Raydium-style swap pool for local tests
+/// Implements the swap adapter interface used by the vault program:
+/// swap(amount_in, minimum_amount_out)
+/// Each pool trades `mint_in` for `mint_out` at a fixed rate (bps of output
+/// per input token), set by the pool authority
+#[program]
+pub mod mock_swap {
+    use super::*;
+    
+    /// Create a one-way pool from `mint_in` to `mint_out` with its two vaults
+    pub fn init_pool(ctx: Context<InitPool>, rate_bps: u16) -> Result<()> {
+        require!(rate_bps > 0, SwapError::InvalidAmount);
+    
+        let pool = &mut ctx.accounts.pool;
+        pool.authority = ctx.accounts.authority.key();
+        pool.mint_in = ctx.accounts.mint_in.key();
+        pool.mint_out = ctx.accounts.mint_out.key();
+        pool.pool_source = ctx.accounts.pool_source.key();
+        pool.pool_destination = ctx.accounts.pool_destination.key();
+        pool.rate_bps = rate_bps;
+        pool.bump = ctx.bumps.pool;
+    
+        Ok(())
+    }
+    
+    /// Change the pool's rate (tests simulate bad fills with this)
+    pub fn set_rate(ctx: Context<SetRate>, rate_bps: u16) -> Result<()> {
+        require!(rate_bps > 0, SwapError::InvalidAmount);
+        ctx.accounts.pool.rate_bps = rate_bps;
+        Ok(())
+    }
+    
+    /// Swap `amount_in` into the pool and pay out `amount_in * rate_bps / 10000`
+    /// Fails if the output is below `minimum_amount_out`
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, minimum_amount_out: u64) -> Result<()> {
+        require!(amount_in > 0, SwapError::InvalidAmount);
+    
+        let pool = &ctx.accounts.pool;
+        let amount_out = ((amount_in as u128)
+            .checked_mul(pool.rate_bps as u128)
+            .ok_or(SwapError::MathOverflow)?
+            / 10000) as u64;
+        require!(amount_out >= minimum_amount_out, SwapError::SlippageExceeded);
+    
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.pool_source.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount_in)?;
+    
+        let seeds = &[
+            b"swap_pool".as_ref(),
+            pool.mint_in.as_ref(),
+            pool.mint_out.as_ref(),
+            &[pool.bump],
+        ];
+        let signer = &[&seeds[..]];
+    
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.pool_destination.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token::transfer(cpi_ctx, amount_out)
+    }
+}
+
+#[account]
+pub struct SwapPool {
+    pub authority: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    pub pool_source: Pubkey,
+    pub pool_destination: Pubkey,
+    pub rate_bps: u16,
+    pub bump: u8,
+}
+
+impl SwapPool {
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 2 + 1;
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SwapPool::LEN,
+        seeds = [b"swap_pool", mint_in.key().as_ref(), mint_out.key().as_ref()],
+        bump
+    )]
+    pub pool: Account<'info, SwapPool>,
+    
+    pub mint_in: Account<'info, Mint>,
+    
+    pub mint_out: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_source", pool.key().as_ref()],
+        bump,
+        token::mint = mint_in,
+        token::authority = pool
+    )]
+    pub pool_source: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"pool_destination", pool.key().as_ref()],
+        bump,
+        token::mint = mint_out,
+        token::authority = pool
+    )]
+    pub pool_destination: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    #[account(
+        mut,
+        has_one = authority
+    )]
+    pub pool: Account<'info, SwapPool>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Account order is part of the adapter interface
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, SwapPool>,
+    
+    #[account(
+        mut,
+        address = pool.pool_source
+    )]
+    pub pool_source: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = pool.pool_destination
+    )]
+    pub pool_destination: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = source.mint == pool.mint_in @ SwapError::InvalidMint
+    )]
+    pub source: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = destination.mint == pool.mint_out @ SwapError::InvalidMint
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[error_code]
+pub enum SwapError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    
+    #[msg("Token account mint does not match the pool")]
+    InvalidMint,
+    
+    #[msg("Output below the minimum amount out")]
+    SlippageExceeded,
+    
+    #[msg("Math overflow")]
+    MathOverflow,
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use crate::errors::VaultError;
+use super::invoke_adapter;
+
+/// Pool state exposed by CLMM adapters
+/// (Anchor account layout: discriminator followed by these fields)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ClmmPool {
+    pub mint: Pubkey,
+    pub token_vault: Pubkey,
+    /// Tick of the current pool price
+    pub tick_current: i32,
+}
+
+/// Position state exposed by CLMM adapters
+/// (Anchor account layout: discriminator followed by these fields)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ClmmPosition {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u64,
+    /// Trading fees accrued to the position and not yet collected
+    pub fees_owed: u64,
+}
+
+/// Accounts for opening a CLMM position on the adapter
+pub struct OpenClmmPositionAccounts<'info> {
+    pub pool: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    /// Position owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Accounts shared by the adapter's liquidity and fee instructions
+pub struct ClmmLiquidityAccounts<'info> {
+    /// Pool state
+    pub pool: AccountInfo<'info>,
+    
+    /// Vault-owned position
+    pub position: AccountInfo<'info>,
+    
+    /// Pool token account holding liquidity and fees
+    pub token_vault: AccountInfo<'info>,
+    
+    /// Vault-owned token account funds move to or from
+    pub token_account: AccountInfo<'info>,
+    
+    /// Position owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Open an empty position owned by the vault over [tick_lower, tick_upper)
+pub fn open_position<'info>(
+    clmm_program: &AccountInfo<'info>,
+    accounts: OpenClmmPositionAccounts<'info>,
+    tick_lower: i32,
+    tick_upper: i32,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.pool.key(), false),
+        AccountMeta::new(accounts.position.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    let infos = [
+        accounts.pool,
+        accounts.position,
+        accounts.owner,
+        accounts.payer,
+        accounts.system_program,
+        clmm_program.clone(),
+    ];
+    
+    invoke_adapter(clmm_program, "open_position", &(tick_lower, tick_upper), metas, &infos, signer_seeds)
+}
+
+/// Add liquidity from the vault token account to the position
+pub fn increase_liquidity<'info>(
+    clmm_program: &AccountInfo<'info>,
+    accounts: ClmmLiquidityAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_liquidity(clmm_program, "increase_liquidity", accounts, &amount, signer_seeds)
+}
+
+/// Remove liquidity from the position into the vault token account
+pub fn decrease_liquidity<'info>(
+    clmm_program: &AccountInfo<'info>,
+    accounts: ClmmLiquidityAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_liquidity(clmm_program, "decrease_liquidity", accounts, &amount, signer_seeds)
+}
+
+/// Collect the position's accrued fees into the vault token account
+pub fn collect_fees<'info>(
+    clmm_program: &AccountInfo<'info>,
+    accounts: ClmmLiquidityAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_liquidity(clmm_program, "collect_fees", accounts, &(), signer_seeds)
+}
+
+/// Move an empty position to a new range
+pub fn update_range<'info>(
+    clmm_program: &AccountInfo<'info>,
+    pool: AccountInfo<'info>,
+    position: AccountInfo<'info>,
+    owner: AccountInfo<'info>,
+    tick_lower: i32,
+    tick_upper: i32,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(pool.key(), false),
+        AccountMeta::new(position.key(), false),
+        AccountMeta::new_readonly(owner.key(), true),
+    ];
+    let infos = [pool, position, owner, clmm_program.clone()];
+    
+    invoke_adapter(clmm_program, "update_range", &(tick_lower, tick_upper), metas, &infos, signer_seeds)
+}
+
+/// Read a pool owned by the CLMM program
+pub fn read_pool(
+    pool: &AccountInfo,
+    clmm_program: &Pubkey,
+) -> Result<ClmmPool> {
+    read_clmm_account(pool, clmm_program)
+}
+
+/// Read a position owned by the CLMM program
+pub fn read_position(
+    position: &AccountInfo,
+    clmm_program: &Pubkey,
+) -> Result<ClmmPosition> {
+    read_clmm_account(position, clmm_program)
+}
+
+fn read_clmm_account<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    clmm_program: &Pubkey,
+) -> Result<T> {
+    require!(
+        account.owner == clmm_program,
+        VaultError::InvalidClmmAccount
+    );
+    
+    let data = account.try_borrow_data()?;
+    require!(data.len() >= 8, VaultError::InvalidClmmAccount);
+    
+    T::deserialize(&mut &data[8..])
+        .map_err(|_| VaultError::InvalidClmmAccount.into())
+}
+
+fn invoke_liquidity<'info, T: AnchorSerialize>(
+    clmm_program: &AccountInfo<'info>,
+    name: &str,
+    accounts: ClmmLiquidityAccounts<'info>,
+    args: &T,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.pool.key(), false),
+        AccountMeta::new(accounts.position.key(), false),
+        AccountMeta::new(accounts.token_vault.key(), false),
+        AccountMeta::new(accounts.token_account.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    let infos = [
+        accounts.pool,
+        accounts.position,
+        accounts.token_vault,
+        accounts.token_account,
+        accounts.owner,
+        accounts.token_program,
+        clmm_program.clone(),
+    ];
+    
+    invoke_adapter(clmm_program, name, args, metas, &infos, signer_seeds)
+}
@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use crate::errors::VaultError;
+use super::invoke_adapter;
+
+/// Obligation state exposed by lending adapters
+/// (Anchor account layout: discriminator followed by these fields)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct LendingObligation {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub deposited_amount: u64,
+    pub borrowed_amount: u64,
+}
+
+/// Accounts shared by the lending adapter's collateral and liquidity instructions
+pub struct LendingAccounts<'info> {
+    /// Lending market (reserve) state
+    pub market: AccountInfo<'info>,
+    
+    /// Obligation tracking the position's collateral and debt
+    pub obligation: AccountInfo<'info>,
+    
+    /// Market token account holding supplied liquidity
+    pub liquidity_supply: AccountInfo<'info>,
+    
+    /// Vault-owned token account funds move to or from
+    pub token_account: AccountInfo<'info>,
+    
+    /// Obligation owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Accounts for opening an obligation on the lending adapter
+pub struct InitObligationAccounts<'info> {
+    pub market: AccountInfo<'info>,
+    pub obligation: AccountInfo<'info>,
+    /// Obligation owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    /// Account distinguishing obligations of the same owner (the user position)
+    pub seed: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Open an obligation owned by the vault for a single user position
+pub fn init_obligation<'info>(
+    lending_program: &AccountInfo<'info>,
+    accounts: InitObligationAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.market.key(), false),
+        AccountMeta::new(accounts.obligation.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new_readonly(accounts.seed.key(), false),
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    let infos = [
+        accounts.market,
+        accounts.obligation,
+        accounts.owner,
+        accounts.seed,
+        accounts.payer,
+        accounts.system_program,
+        lending_program.clone(),
+    ];
+    
+    invoke_adapter(lending_program, "init_obligation", &(), metas, &infos, signer_seeds)
+}
+
+/// Deposit collateral from the vault token account into the obligation
+pub fn deposit_collateral<'info>(
+    lending_program: &AccountInfo<'info>,
+    accounts: LendingAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_lending(lending_program, "deposit_collateral", accounts, amount, signer_seeds)
+}
+
+/// Withdraw collateral from the obligation into the vault token account
+pub fn withdraw_collateral<'info>(
+    lending_program: &AccountInfo<'info>,
+    accounts: LendingAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_lending(lending_program, "withdraw_collateral", accounts, amount, signer_seeds)
+}
+
+/// Borrow liquidity against the obligation into the vault token account
+pub fn borrow<'info>(
+    lending_program: &AccountInfo<'info>,
+    accounts: LendingAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_lending(lending_program, "borrow", accounts, amount, signer_seeds)
+}
+
+/// Repay obligation debt from the vault token account
+pub fn repay<'info>(
+    lending_program: &AccountInfo<'info>,
+    accounts: LendingAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_lending(lending_program, "repay", accounts, amount, signer_seeds)
+}
+
+/// Read an obligation owned by the lending program
+pub fn read_obligation(
+    obligation: &AccountInfo,
+    lending_program: &Pubkey,
+) -> Result<LendingObligation> {
+    require!(
+        obligation.owner == lending_program,
+        VaultError::InvalidLendingAccount
+    );
+    
+    let data = obligation.try_borrow_data()?;
+    require!(data.len() >= 8, VaultError::InvalidLendingAccount);
+    
+    LendingObligation::deserialize(&mut &data[8..])
+        .map_err(|_| VaultError::InvalidLendingAccount.into())
+}
+
+fn invoke_lending<'info>(
+    lending_program: &AccountInfo<'info>,
+    name: &str,
+    accounts: LendingAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new(accounts.market.key(), false),
+        AccountMeta::new(accounts.obligation.key(), false),
+        AccountMeta::new(accounts.liquidity_supply.key(), false),
+        AccountMeta::new(accounts.token_account.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    let infos = [
+        accounts.market,
+        accounts.obligation,
+        accounts.liquidity_supply,
+        accounts.token_account,
+        accounts.owner,
+        accounts.token_program,
+        lending_program.clone(),
+    ];
+    
+    invoke_adapter(lending_program, name, &amount, metas, &infos, signer_seeds)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+pub mod clmm;
+pub mod lending;
+pub mod perp;
+pub mod stake_pool;
+pub mod swap;
+
+pub use swap::*;
+
+/// Compute the 8-byte Anchor instruction discriminator for an adapter instruction
+/// Formula: sha256("global:<name>")[..8]
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    let preimage = format!("global:{}", name);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(preimage.as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+/// Invoke an adapter program instruction using Anchor's instruction encoding
+/// (discriminator followed by Borsh-serialized arguments), signing with the vault PDA
+pub fn invoke_adapter<'info, T: AnchorSerialize>(
+    program: &AccountInfo<'info>,
+    name: &str,
+    args: &T,
+    accounts: Vec<AccountMeta>,
+    account_infos: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut data = instruction_discriminator(name).to_vec();
+    args.serialize(&mut data)?;
+    
+    let ix = Instruction {
+        program_id: program.key(),
+        accounts,
+        data,
+    };
+    invoke_signed(&ix, account_infos, signer_seeds)?;
+    
+    Ok(())
+}
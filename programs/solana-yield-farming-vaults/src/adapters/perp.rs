@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use crate::errors::VaultError;
+use super::invoke_adapter;
+
+/// Perp position state exposed by perp adapters
+/// (Anchor account layout: discriminator followed by these fields)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PerpPosition {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    /// Signed base size (negative = short)
+    pub base_size: i64,
+    pub margin: u64,
+}
+
+/// Accounts for opening a perp position on the perp adapter
+pub struct InitPerpPositionAccounts<'info> {
+    pub market: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    /// Position owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Accounts for moving margin between the vault and a perp position
+pub struct PerpMarginAccounts<'info> {
+    /// Perp market state
+    pub market: AccountInfo<'info>,
+    
+    /// Position the margin is posted to
+    pub position: AccountInfo<'info>,
+    
+    /// Market token account holding posted margin
+    pub margin_vault: AccountInfo<'info>,
+    
+    /// Vault-owned token account funds move to or from
+    pub token_account: AccountInfo<'info>,
+    
+    /// Position owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+    
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Accounts for changing a perp position's size
+pub struct PerpOrderAccounts<'info> {
+    pub market: AccountInfo<'info>,
+    pub position: AccountInfo<'info>,
+    /// Position owner (the vault PDA)
+    pub owner: AccountInfo<'info>,
+}
+
+/// Open a perp position owned by the vault
+pub fn init_position<'info>(
+    perp_program: &AccountInfo<'info>,
+    accounts: InitPerpPositionAccounts<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.market.key(), false),
+        AccountMeta::new(accounts.position.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+    ];
+    let infos = [
+        accounts.market,
+        accounts.position,
+        accounts.owner,
+        accounts.payer,
+        accounts.system_program,
+        perp_program.clone(),
+    ];
+    
+    invoke_adapter(perp_program, "init_position", &(), metas, &infos, signer_seeds)
+}
+
+/// Post margin from the vault token account to the position
+pub fn deposit_margin<'info>(
+    perp_program: &AccountInfo<'info>,
+    accounts: PerpMarginAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_margin(perp_program, "deposit_margin", accounts, amount, signer_seeds)
+}
+
+/// Withdraw margin from the position into the vault token account
+pub fn withdraw_margin<'info>(
+    perp_program: &AccountInfo<'info>,
+    accounts: PerpMarginAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_margin(perp_program, "withdraw_margin", accounts, amount, signer_seeds)
+}
+
+/// Change the position's base size (negative delta = sell), failing inside the
+/// adapter if the fill price is worse than `limit_price` (1e8 scale)
+pub fn place_order<'info>(
+    perp_program: &AccountInfo<'info>,
+    accounts: PerpOrderAccounts<'info>,
+    base_delta: i64,
+    limit_price: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.market.key(), false),
+        AccountMeta::new(accounts.position.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+    ];
+    let infos = [
+        accounts.market,
+        accounts.position,
+        accounts.owner,
+        perp_program.clone(),
+    ];
+    
+    invoke_adapter(
+        perp_program,
+        "place_order",
+        &(base_delta, limit_price),
+        metas,
+        &infos,
+        signer_seeds,
+    )
+}
+
+/// Read a perp position owned by the perp program
+pub fn read_position(
+    position: &AccountInfo,
+    perp_program: &Pubkey,
+) -> Result<PerpPosition> {
+    require!(
+        position.owner == perp_program,
+        VaultError::InvalidHedgeAccount
+    );
+    
+    let data = position.try_borrow_data()?;
+    require!(data.len() >= 8, VaultError::InvalidHedgeAccount);
+    
+    PerpPosition::deserialize(&mut &data[8..])
+        .map_err(|_| VaultError::InvalidHedgeAccount.into())
+}
+
+fn invoke_margin<'info>(
+    perp_program: &AccountInfo<'info>,
+    name: &str,
+    accounts: PerpMarginAccounts<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new_readonly(accounts.market.key(), false),
+        AccountMeta::new(accounts.position.key(), false),
+        AccountMeta::new(accounts.margin_vault.key(), false),
+        AccountMeta::new(accounts.token_account.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    let infos = [
+        accounts.market,
+        accounts.position,
+        accounts.margin_vault,
+        accounts.token_account,
+        accounts.owner,
+        accounts.token_program,
+        perp_program.clone(),
+    ];
+    
+    invoke_adapter(perp_program, name, &amount, metas, &infos, signer_seeds)
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::errors::VaultError;
+
+/// SPL stake pool program (jitoSOL and other SPL-based liquid staking tokens)
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// `AccountType::StakePool` tag of an initialized stake pool account
+const STAKE_POOL_ACCOUNT_TYPE: u8 = 1;
+
+/// Byte offsets into the Borsh-encoded `StakePool` account:
+/// account_type (1), manager, staker, stake_deposit_authority (32 each),
+/// stake_withdraw_bump_seed (1), validator_list, reserve_stake (32 each),
+/// pool_mint (32), manager_fee_account, token_program_id (32 each),
+/// total_lamports (8), pool_token_supply (8)
+const POOL_MINT_OFFSET: usize = 1 + 32 * 3 + 1 + 32 * 2;
+const TOTAL_LAMPORTS_OFFSET: usize = POOL_MINT_OFFSET + 32 * 3;
+const POOL_TOKEN_SUPPLY_OFFSET: usize = TOTAL_LAMPORTS_OFFSET + 8;
+
+/// Stake pool fields needed to price its pool token in SOL
+#[derive(Clone, Copy, Default)]
+pub struct StakePoolState {
+    /// Liquid staking token minted by the pool
+    pub pool_mint: Pubkey,
+    /// SOL (lamports) backing the pool as of the last epoch update
+    pub total_lamports: u64,
+    /// Pool tokens in circulation
+    pub pool_token_supply: u64,
+}
+
+/// Read a stake pool owned by the SPL stake pool program
+pub fn read_stake_pool(stake_pool: &AccountInfo) -> Result<StakePoolState> {
+    require!(
+        stake_pool.owner == &SPL_STAKE_POOL_PROGRAM_ID,
+        VaultError::InvalidStakePool
+    );
+    
+    let data = stake_pool.try_borrow_data()?;
+    require!(
+        data.len() >= POOL_TOKEN_SUPPLY_OFFSET + 8 && data[0] == STAKE_POOL_ACCOUNT_TYPE,
+        VaultError::InvalidStakePool
+    );
+    
+    let read_u64 = |offset: usize| {
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    };
+    
+    Ok(StakePoolState {
+        pool_mint: Pubkey::try_from(&data[POOL_MINT_OFFSET..POOL_MINT_OFFSET + 32])
+            .map_err(|_| VaultError::InvalidStakePool)?,
+        total_lamports: read_u64(TOTAL_LAMPORTS_OFFSET),
+        pool_token_supply: read_u64(POOL_TOKEN_SUPPLY_OFFSET),
+    })
+}
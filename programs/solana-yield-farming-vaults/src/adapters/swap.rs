@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use super::invoke_adapter;
+
+/// Accounts expected by a swap adapter's `swap(amount_in, minimum_amount_out)` instruction
+pub struct SwapAccounts<'info> {
+    /// Pool (or route) state account
+    pub pool: AccountInfo<'info>,
+    
+    /// Pool vault receiving the input token
+    pub pool_source: AccountInfo<'info>,
+    
+    /// Pool vault paying out the output token
+    pub pool_destination: AccountInfo<'info>,
+    
+    /// Vault-owned token account holding the input token
+    pub source: AccountInfo<'info>,
+    
+    /// Vault-owned token account receiving the output token
+    pub destination: AccountInfo<'info>,
+    
+    /// Owner of `source` (the vault PDA)
+    pub authority: AccountInfo<'info>,
+    
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Swap `amount_in` of the source token through the swap adapter, failing
+/// inside the adapter if fewer than `minimum_amount_out` tokens would be received
+pub fn swap<'info>(
+    swap_program: &AccountInfo<'info>,
+    accounts: SwapAccounts<'info>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new(accounts.pool.key(), false),
+        AccountMeta::new(accounts.pool_source.key(), false),
+        AccountMeta::new(accounts.pool_destination.key(), false),
+        AccountMeta::new(accounts.source.key(), false),
+        AccountMeta::new(accounts.destination.key(), false),
+        AccountMeta::new_readonly(accounts.authority.key(), true),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    let infos = [
+        accounts.pool,
+        accounts.pool_source,
+        accounts.pool_destination,
+        accounts.source,
+        accounts.destination,
+        accounts.authority,
+        accounts.token_program,
+        swap_program.clone(),
+    ];
+    
+    invoke_adapter(
+        swap_program,
+        "swap",
+        &(amount_in, minimum_amount_out),
+        metas,
+        &infos,
+        signer_seeds,
+    )
+}
@@ -76,4 +76,106 @@ pub enum VaultError {
     
     #[msg("Compounding cooldown not expired")]
     CompoundingCooldown,
+    
+    #[msg("Invalid swap program")]
+    InvalidSwapProgram,
+    
+    #[msg("Invalid reward token accounts")]
+    InvalidRewardAccounts,
+    
+    #[msg("Deposits are paused")]
+    DepositsPaused,
+    
+    #[msg("Invalid loss report - zero or exceeds allowed bound")]
+    InvalidLossReport,
+    
+    #[msg("Reserve not initialized")]
+    ReserveNotInitialized,
+    
+    #[msg("Reserve withdrawal timelock not expired")]
+    ReserveTimelockActive,
+    
+    #[msg("Invalid lending protocol account")]
+    InvalidLendingAccount,
+    
+    #[msg("Liquidation exceeds close factor")]
+    CloseFactorExceeded,
+    
+    #[msg("Liquidation auctions not enabled for this vault")]
+    AuctionNotEnabled,
+    
+    #[msg("Liquidation auction not started")]
+    AuctionNotStarted,
+    
+    #[msg("Liquidation auction already started")]
+    AuctionAlreadyStarted,
+    
+    #[msg("Auto-deleverage not enabled for this vault")]
+    AutoDeleverageNotEnabled,
+    
+    #[msg("Auto-deleverage not needed - health factor above soft threshold")]
+    DeleverageNotNeeded,
+    
+    #[msg("Invalid perp hedge account")]
+    InvalidHedgeAccount,
+    
+    #[msg("Rehedge not needed - net delta within band")]
+    RehedgeNotNeeded,
+    
+    #[msg("Invalid CLMM pool or position account")]
+    InvalidClmmAccount,
+    
+    #[msg("Invalid tick range")]
+    InvalidTickRange,
+    
+    #[msg("Range reset not needed - price within position range")]
+    PriceInRange,
+    
+    #[msg("Instruction requires a dual-asset vault")]
+    NotDualAssetVault,
+    
+    #[msg("Dual-asset vaults use deposit_pair and withdraw_pair")]
+    DualAssetVault,
+    
+    #[msg("Invalid share token metadata")]
+    InvalidShareMetadata,
+    
+    #[msg("Vault underlying is not the native (wrapped SOL) mint")]
+    NotNativeSolVault,
+    
+    #[msg("Invalid stake pool account")]
+    InvalidStakePool,
+    
+    #[msg("Invalid lock configuration")]
+    InvalidLockConfig,
+    
+    #[msg("Invalid lock tier or tier lower than the existing lock")]
+    InvalidLockTier,
+    
+    #[msg("No shares locked")]
+    NothingLocked,
+    
+    #[msg("Invalid reward emission schedule")]
+    InvalidEmissionSchedule,
+    
+    #[msg("Reward emission funding does not cover the schedule")]
+    InsufficientRewardFunding,
+    
+    #[msg("Reward mint not registered or no free emission slot")]
+    InvalidRewardMint,
+    
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    
+    #[msg("Transfer hook called outside of a share transfer")]
+    NotTransferring,
+    
+    #[msg("Liquidation auction expired; start a new one")]
+    AuctionExpired,
+    
+    #[msg("Strategy venue cannot change while positions are open on it")]
+    VenueInUse,
+    
+    #[msg("Account is not a vault in the original layout")]
+    NotLegacyVault,
 }
@@ -40,12 +40,81 @@ pub struct HarvestEvent {
     pub vault: Pubkey,
     pub harvester: Pubkey,
     pub rewards_harvested: u64,
+    pub rewards_swapped: u64, // Portion of rewards_harvested obtained by swapping reward tokens
+    pub clmm_fees_collected: u64, // Portion of rewards_harvested collected from the CLMM position
+    pub stake_yield: u64, // Portion of rewards_harvested realized through the LST stake rate
+    pub harvester_bounty: u64, // Paid to the harvester out of rewards_harvested
+    pub reserve_contribution: u64, // Routed to the insurance reserve
     pub rewards_reinvested: u64,
+    pub lock_boost: u64, // Portion of rewards_reinvested issued to locked shares as boost
     pub new_total_assets: u64,
     pub apy_estimate: u64, // Basis points (10000 = 100%)
     pub timestamp: i64,
 }
 
+/// Event emitted when a reward token is swapped into the underlying mint during harvest
+#[event]
+pub struct RewardSwapEvent {
+    pub vault: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub min_amount_out: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a strategy loss is reported against the vault
+#[event]
+pub struct LossReportedEvent {
+    pub vault: Pubkey,
+    pub reporter: Pubkey,
+    pub loss_amount: u64,
+    pub reserve_covered: u64, // Covered by the insurance reserve
+    pub buffer_absorbed: u64, // Covered by unvested (locked) profit
+    pub socialized_loss: u64, // Reflected in share price
+    pub new_total_assets: u64,
+    pub deposits_paused: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the insurance reserve receives funds
+#[event]
+pub struct ReserveFundedEvent {
+    pub vault: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub reserve_balance: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the authority queues a reserve withdrawal
+#[event]
+pub struct ReserveWithdrawalRequestedEvent {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub unlock_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a queued reserve withdrawal executes
+#[event]
+pub struct ReserveWithdrawnEvent {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event reporting the current insurance reserve balance
+#[event]
+pub struct ReserveBalanceEvent {
+    pub vault: Pubkey,
+    pub reserve_balance: u64,
+    pub total_assets: u64,
+    pub pending_withdrawal: u64,
+    pub timestamp: i64,
+}
+
 /// Event emitted when a vault position is rebalanced
 #[event]
 pub struct RebalanceEvent {
@@ -67,10 +136,48 @@ pub struct LiquidationEvent {
     pub liquidated_user: Pubkey,
     pub collateral_seized: u64,
     pub debt_repaid: u64,
+    pub protocol_fee: u64, // Protocol cut of the bonus, in collateral
     pub health_factor_before: u64, // Basis points
     pub timestamp: i64,
 }
 
+/// Event emitted when a liquidation auction starts
+#[event]
+pub struct LiquidationAuctionStartedEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub starter: Pubkey,
+    pub health_factor: u64, // Basis points
+    pub max_bonus_bps: u16,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a liquidation auction is filled
+#[event]
+pub struct LiquidationAuctionFilledEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub liquidator: Pubkey,
+    pub bonus_bps: u16,
+    pub auction_elapsed: i64,
+    pub collateral_seized: u64,
+    pub debt_repaid: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when residual debt of an exhausted position is written off
+#[event]
+pub struct BadDebtEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub bad_debt: u64, // In debt token
+    pub reserve_covered: u64,
+    pub socialized_loss: u64,
+    pub new_total_assets: u64,
+    pub timestamp: i64,
+}
+
 /// Event emitted when fees are collected
 #[event]
 pub struct FeeCollectionEvent {
@@ -91,5 +198,161 @@ pub struct LeverageAdjustmentEvent {
     pub leverage_after: u64,
     pub collateral_added: u64,
     pub debt_added: u64,
+    pub collateral_removed: u64,
+    pub debt_repaid: u64,
+    pub health_factor_after: u64, // Basis points
+    pub timestamp: i64,
+}
+
+/// Event emitted when a keeper deleverages an at-risk position
+#[event]
+pub struct AutoDeleverageEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub leverage_before: u16, // Basis points
+    pub leverage_after: u16,
+    pub debt_repaid: u64,
+    pub keeper_fee: u64,
+    pub health_factor_before: u64, // Basis points
+    pub health_factor_after: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted with a vault-wide health summary
+#[event]
+pub struct VaultHealthEvent {
+    pub vault: Pubkey,
+    pub total_collateral: u64,
+    pub total_debt: u64,
+    pub leverage_bps: u16,
+    pub max_leverage_bps: u16,
+    pub health_factor: u64, // Basis points
+    pub liquidation_threshold_bps: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a delta-neutral vault's perp hedge is resized
+#[event]
+pub struct RehedgeEvent {
+    pub vault: Pubkey,
+    pub keeper: Pubkey,
+    pub spot_exposure: u64,
+    pub hedge_size_before: i64,
+    pub hedge_size_after: i64,
+    pub net_delta_before: i64,
+    pub net_delta_after: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a CLMM position is recentered on the current price
+#[event]
+pub struct RangeResetEvent {
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub tick_current: i32,
+    pub old_tick_lower: i32,
+    pub old_tick_upper: i32,
+    pub new_tick_lower: i32,
+    pub new_tick_upper: i32,
+    pub liquidity: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user deposits both legs into a dual-asset vault
+#[event]
+pub struct PairDepositEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub swapped_in: u64, // Amount swapped to match the vault ratio (0 if no swap)
+    pub swapped_a_to_b: bool,
+    pub deposit_value: u64, // Value of both legs in token A units
+    pub shares_minted: u64,
+    pub total_assets: u64,
+    pub total_assets_b: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user withdraws both legs from a dual-asset vault
+#[event]
+pub struct PairWithdrawEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub shares_burned: u64,
+    pub total_assets: u64,
+    pub total_assets_b: u64,
+    pub total_shares: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the vault authority updates share token metadata
+#[event]
+pub struct ShareMetadataUpdated {
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user locks vault shares
+#[event]
+pub struct SharesLockedEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub shares: u64,
+    pub total_locked: u64,
+    pub tier: u8,
+    pub weight: u64,
+    pub unlock_ts: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user unlocks their locked shares
+#[event]
+pub struct SharesUnlockedEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub shares_returned: u64,
+    pub penalty_shares: u64,
+    pub boost_shares: u64,
+    pub early: bool,
+    pub timestamp: i64,
+}
+
+/// Event emitted when the vault authority funds or reschedules a reward emission
+#[event]
+pub struct RewardEmissionUpdatedEvent {
+    pub vault: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount_funded: u64,
+    pub emissions_per_second: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a user claims emission rewards
+#[event]
+pub struct RewardsClaimedEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a liquidation auction ends without a fill because the position recovered
+#[event]
+pub struct LiquidationAuctionCancelledEvent {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub health_factor: u64, // Basis points
     pub timestamp: i64,
 }
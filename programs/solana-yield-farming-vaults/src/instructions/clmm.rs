@@ -0,0 +1,356 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::adapters::clmm::{
+    self, read_pool, read_position, ClmmLiquidityAccounts, OpenClmmPositionAccounts,
+};
+use crate::utils::calculate_centered_range;
+use crate::events::RangeResetEvent;
+
+/// Open the vault's concentrated-liquidity position in `pool_address` and
+/// deploy liquidity from the vault token account (LP Farming, or the LP leg
+/// a Delta-Neutral vault hedges). Deployed liquidity stays part of total assets
+pub fn open_clmm_position(
+    ctx: Context<OpenClmmPosition>,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity_amount: u64,
+) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can open the position
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(vault.strategy == 0 || vault.strategy == 3, VaultError::InvalidStrategy);
+    require!(
+        vault.strategy_config.clmm_position.is_none(),
+        VaultError::InvalidClmmAccount
+    );
+    require!(tick_lower < tick_upper, VaultError::InvalidTickRange);
+    validate_clmm_accounts(
+        vault,
+        &ctx.accounts.clmm_program.key(),
+        &ctx.accounts.pool.key(),
+    )?;
+    
+    let clmm_program = ctx.accounts.clmm_program.to_account_info();
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    clmm::open_position(
+        &clmm_program,
+        OpenClmmPositionAccounts {
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            owner: vault_info.clone(),
+            payer: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        tick_lower,
+        tick_upper,
+        signer,
+    )?;
+    
+    if liquidity_amount > 0 {
+        clmm::increase_liquidity(
+            &clmm_program,
+            ClmmLiquidityAccounts {
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                token_vault: ctx.accounts.pool_token_vault.to_account_info(),
+                token_account: ctx.accounts.vault_token_account.to_account_info(),
+                owner: vault_info,
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            liquidity_amount,
+            signer,
+        )?;
+    }
+    
+    vault.strategy_config.clmm_position = Some(ctx.accounts.position.key());
+    vault.strategy_config.tick_lower = tick_lower;
+    vault.strategy_config.tick_upper = tick_upper;
+    
+    msg!("Opened CLMM position [{}, {}) for vault {} with {} liquidity", 
+         tick_lower, tick_upper, vault.vault_id, liquidity_amount);
+    
+    Ok(())
+}
+
+/// Move liquidity between the vault token account and the CLMM position
+/// (e.g., to free tokens for withdrawals or deploy new deposits)
+pub fn adjust_clmm_liquidity(
+    ctx: Context<AdjustClmmLiquidity>,
+    amount: u64,
+    increase: bool,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    // Only vault authority can move liquidity
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(amount > 0, VaultError::InsufficientFunds);
+    validate_clmm_accounts(
+        vault,
+        &ctx.accounts.clmm_program.key(),
+        &ctx.accounts.pool.key(),
+    )?;
+    require!(
+        Some(ctx.accounts.position.key()) == vault.strategy_config.clmm_position,
+        VaultError::InvalidClmmAccount
+    );
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let clmm_program = ctx.accounts.clmm_program.to_account_info();
+    let accounts = ClmmLiquidityAccounts {
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        token_vault: ctx.accounts.pool_token_vault.to_account_info(),
+        token_account: ctx.accounts.vault_token_account.to_account_info(),
+        owner: vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    
+    if increase {
+        clmm::increase_liquidity(&clmm_program, accounts, amount, signer)?;
+    } else {
+        clmm::decrease_liquidity(&clmm_program, accounts, amount, signer)?;
+    }
+    
+    msg!("{} CLMM liquidity by {} for vault {}", 
+         if increase { "Increased" } else { "Decreased" }, amount, vault.vault_id);
+    
+    Ok(())
+}
+
+/// Recenter the CLMM position on the current price once it leaves the range
+/// Withdraws all liquidity, moves the range (same width) and redeploys it.
+/// Restricted to the vault authority, since the spot tick it recenters on can
+/// be pushed within a transaction. Accrued fees stay in the position until the next harvest
+pub fn reset_range(ctx: Context<ResetRange>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can move the range
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(!vault.paused, VaultError::VaultPaused);
+    validate_clmm_accounts(
+        vault,
+        &ctx.accounts.clmm_program.key(),
+        &ctx.accounts.pool.key(),
+    )?;
+    require!(
+        Some(ctx.accounts.position.key()) == vault.strategy_config.clmm_position,
+        VaultError::InvalidClmmAccount
+    );
+    
+    let clmm_program_key = ctx.accounts.clmm_program.key();
+    let pool = read_pool(&ctx.accounts.pool.to_account_info(), &clmm_program_key)?;
+    let position = read_position(&ctx.accounts.position.to_account_info(), &clmm_program_key)?;
+    require!(
+        position.owner == vault.key() && position.pool == ctx.accounts.pool.key(),
+        VaultError::InvalidClmmAccount
+    );
+    
+    // Only out-of-range positions are reset
+    let old_tick_lower = position.tick_lower;
+    let old_tick_upper = position.tick_upper;
+    require!(
+        pool.tick_current < old_tick_lower || pool.tick_current >= old_tick_upper,
+        VaultError::PriceInRange
+    );
+    
+    let (new_tick_lower, new_tick_upper) = calculate_centered_range(
+        pool.tick_current,
+        old_tick_lower,
+        old_tick_upper,
+    )?;
+    
+    let clmm_program = ctx.accounts.clmm_program.to_account_info();
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let liquidity_accounts = || ClmmLiquidityAccounts {
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        token_vault: ctx.accounts.pool_token_vault.to_account_info(),
+        token_account: ctx.accounts.vault_token_account.to_account_info(),
+        owner: vault_info.clone(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    
+    if position.liquidity > 0 {
+        clmm::decrease_liquidity(&clmm_program, liquidity_accounts(), position.liquidity, signer)?;
+    }
+    
+    clmm::update_range(
+        &clmm_program,
+        ctx.accounts.pool.to_account_info(),
+        ctx.accounts.position.to_account_info(),
+        vault_info.clone(),
+        new_tick_lower,
+        new_tick_upper,
+        signer,
+    )?;
+    
+    if position.liquidity > 0 {
+        clmm::increase_liquidity(&clmm_program, liquidity_accounts(), position.liquidity, signer)?;
+    }
+    
+    vault.strategy_config.tick_lower = new_tick_lower;
+    vault.strategy_config.tick_upper = new_tick_upper;
+    vault.last_rebalance = clock.unix_timestamp;
+    
+    emit!(RangeResetEvent {
+        vault: vault.key(),
+        authority: ctx.accounts.authority.key(),
+        tick_current: pool.tick_current,
+        old_tick_lower,
+        old_tick_upper,
+        new_tick_lower,
+        new_tick_upper,
+        liquidity: position.liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reset range for vault {}: [{}, {}) -> [{}, {})", 
+         vault.vault_id, old_tick_lower, old_tick_upper, new_tick_lower, new_tick_upper);
+    
+    Ok(())
+}
+
+/// Check the CLMM program and pool against the vault's strategy config
+pub fn validate_clmm_accounts(
+    vault: &Vault,
+    clmm_program: &Pubkey,
+    pool: &Pubkey,
+) -> Result<()> {
+    require!(
+        Some(*clmm_program) == vault.strategy_config.clmm_program,
+        VaultError::InvalidClmmAccount
+    );
+    require!(
+        Some(*pool) == vault.strategy_config.pool_address,
+        VaultError::InvalidClmmAccount
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenClmmPosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: CLMM adapter program, validated against the strategy config
+    pub clmm_program: UncheckedAccount<'info>,
+    
+    /// CHECK: CLMM pool, validated against strategy_config.pool_address
+    pub pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Position to open, created and validated by the CLMM program
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool token vault, validated by the CLMM program
+    #[account(mut)]
+    pub pool_token_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdjustClmmLiquidity<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: CLMM adapter program, validated against the strategy config
+    pub clmm_program: UncheckedAccount<'info>,
+    
+    /// CHECK: CLMM pool, validated against strategy_config.pool_address
+    pub pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault's position, validated against the strategy config
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool token vault, validated by the CLMM program
+    #[account(mut)]
+    pub pool_token_vault: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ResetRange<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: CLMM adapter program, validated against the strategy config
+    pub clmm_program: UncheckedAccount<'info>,
+    
+    /// CHECK: CLMM pool, validated against strategy_config.pool_address and read in handler
+    pub pool: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault's position, validated against the strategy config and read in handler
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+    
+    /// CHECK: Pool token vault, validated by the CLMM program
+    #[account(mut)]
+    pub pool_token_vault: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,20 +1,76 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::VaultError;
-use crate::utils::calculate_shares_to_mint;
+use crate::utils::{
+    calculate_locked_profit, calculate_shares_to_mint, underlying_to_assets,
+};
 use crate::events::DepositEvent;
+use super::position::{held_shares, track_deposit};
+use super::rewards::checkpoint_rewards;
 
 /// Deposit assets into a vault and receive shares
 pub fn deposit(
     ctx: Context<Deposit>,
     amount: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
+    let source = ctx.accounts.user_token_account.to_account_info();
+    
+    deposit_into_vault(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            underlying_mint: &ctx.accounts.underlying_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            share_mint: &ctx.accounts.share_mint,
+            user_share_account: &ctx.accounts.user_share_account,
+            share_token_program: &ctx.accounts.share_token_program,
+            user: &ctx.accounts.user,
+            user_position: &ctx.accounts.user_position,
+            user_lock: &ctx.accounts.user_lock,
+            reward_distributor: &ctx.accounts.reward_distributor,
+            user_rewards: &ctx.accounts.user_rewards,
+        },
+        source,
+        amount,
+    )
+}
+
+/// Accounts a deposit moves underlying tokens and mints shares through
+/// (shared by `deposit` and `deposit_sol`)
+pub(crate) struct DepositAccounts<'a, 'info> {
+    pub vault: &'a mut Account<'info, Vault>,
+    pub vault_token_account: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub underlying_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: AccountInfo<'info>,
+    pub share_mint: &'a InterfaceAccount<'info, Mint>,
+    pub user_share_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: &'a Interface<'info, TokenInterface>,
+    pub user: &'a Signer<'info>,
+    pub user_position: &'a UncheckedAccount<'info>,
+    pub user_lock: &'a UncheckedAccount<'info>,
+    pub reward_distributor: &'a UncheckedAccount<'info>,
+    pub user_rewards: &'a UncheckedAccount<'info>,
+}
+
+/// Move `amount` underlying tokens from `source` (owned by the user) into the
+/// vault, mint shares for what arrived and update vault accounting, the
+/// depositor's position and the high-water mark
+pub(crate) fn deposit_into_vault<'info>(
+    accounts: DepositAccounts<'_, 'info>,
+    source: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let vault = accounts.vault;
     let clock = Clock::get()?;
     
     // Check vault is not paused
     require!(!vault.paused, VaultError::VaultPaused);
+    require!(!vault.deposits_paused, VaultError::DepositsPaused);
+    require!(
+        vault.token_b_mint == Pubkey::default(),
+        VaultError::DualAssetVault
+    );
     
     // Check minimum deposit
     require!(
@@ -22,50 +78,75 @@ pub fn deposit(
         VaultError::DepositTooSmall
     );
     
-    // Calculate shares to mint
+    // Transfer tokens from user to vault
+    let balance_before = accounts.vault_token_account.amount;
+    let cpi_accounts = TransferChecked {
+        from: source,
+        mint: accounts.underlying_mint.to_account_info(),
+        to: accounts.vault_token_account.to_account_info(),
+        authority: accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(accounts.token_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, accounts.underlying_mint.decimals)?;
+    
+    // Credit only what arrived (Token-2022 transfer fees are withheld from the recipient)
+    accounts.vault_token_account.reload()?;
+    let amount_received = accounts.vault_token_account.amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Liquid staking vaults account in SOL at the last realized stake rate
+    let deposit_value = underlying_to_assets(vault, amount_received)?;
+    
+    // Calculate shares to mint (locked profit does not count toward share price yet)
+    let locked_profit = calculate_locked_profit(vault, clock.unix_timestamp)?;
     let shares_to_mint = calculate_shares_to_mint(
-        amount,
+        deposit_value,
         vault.total_assets,
+        locked_profit,
         vault.total_shares,
     )?;
     
     require!(shares_to_mint > 0, VaultError::MathOverflow);
     
-    // Transfer tokens from user to vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.user_token_account.to_account_info(),
-        to: ctx.accounts.vault_token_account.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::transfer(cpi_ctx, amount)?;
-    
     // Mint vault shares to user
-    let seeds = &[
-        b"vault",
-        &vault.vault_id.to_le_bytes(),
-        &[vault.bump],
-    ];
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
     let signer = &[&seeds[..]];
     
     let cpi_accounts = MintTo {
-        mint: ctx.accounts.share_mint.to_account_info(),
-        to: ctx.accounts.user_share_account.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
+        mint: accounts.share_mint.to_account_info(),
+        to: accounts.user_share_account.to_account_info(),
+        authority: vault.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = accounts.share_token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::mint_to(cpi_ctx, shares_to_mint)?;
+    token_interface::mint_to(cpi_ctx, shares_to_mint)?;
+    
+    // Minted shares start earning emission rewards
+    checkpoint_rewards(
+        accounts.reward_distributor,
+        accounts.user_rewards,
+        shares_to_mint,
+        0,
+    )?;
     
     // Update vault state
     vault.total_assets = vault.total_assets
-        .checked_add(amount)
+        .checked_add(deposit_value)
         .ok_or(VaultError::MathOverflow)?;
     vault.total_shares = vault.total_shares
         .checked_add(shares_to_mint)
         .ok_or(VaultError::MathOverflow)?;
     
+    track_deposit(
+        &accounts.user_position.to_account_info(),
+        held_shares(accounts.user_share_account, &accounts.user_lock.to_account_info())?,
+        deposit_value,
+        shares_to_mint,
+        clock.unix_timestamp,
+    )?;
+    
     // Update high water mark if this is first deposit or NAV increased
     let nav_per_share = if vault.total_shares > 0 {
         vault.total_assets
@@ -81,15 +162,16 @@ pub fn deposit(
     
     emit!(DepositEvent {
         vault: vault.key(),
-        user: ctx.accounts.user.key(),
-        amount,
+        user: accounts.user.key(),
+        amount: amount_received,
         shares_minted: shares_to_mint,
         total_assets: vault.total_assets,
         total_shares: vault.total_shares,
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Deposited {} tokens, minted {} shares", amount, shares_to_mint);
+    msg!("Deposited {} tokens ({} received), minted {} shares", 
+         amount, amount_received, shares_to_mint);
     
     Ok(())
 }
@@ -108,30 +190,74 @@ pub struct Deposit<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = user_token_account.mint == vault.underlying_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint,
+        mint::token_program = token_program
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
-        constraint = share_mint.key() == vault.share_mint
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub share_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         constraint = user_share_account.mint == vault.share_mint,
         constraint = user_share_account.owner == user.key()
     )]
-    pub user_share_account: Account<'info, TokenAccount>,
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// Token program of the underlying mint (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Depositor's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,
 }
@@ -1,32 +1,109 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token::accessor;
 use crate::state::*;
 use crate::errors::VaultError;
-use crate::utils::{can_harvest, calculate_management_fee, calculate_performance_fee, estimate_apy};
-use crate::events::{HarvestEvent, FeeCollectionEvent};
+use crate::adapters::{swap, SwapAccounts};
+use crate::adapters::clmm::{self, read_position, ClmmLiquidityAccounts};
+use crate::utils::{
+    assets_to_underlying, can_harvest, calculate_locked_profit, calculate_management_fee,
+    calculate_performance_fee, calculate_min_swap_output, estimate_apy, get_oracle_price,
+    underlying_to_assets, validate_oracle_price,
+};
+use crate::events::{HarvestEvent, FeeCollectionEvent, ReserveFundedEvent, RewardSwapEvent};
+use super::clmm::validate_clmm_accounts;
+use super::liquid_staking::realize_stake_yield;
+use super::lock::allocate_lock_boost;
+
+/// Number of remaining accounts passed per reward token swap:
+/// [reward_token_account, reward_mint, pool, pool_source, pool_destination]
+pub const REWARD_SWAP_ACCOUNTS: usize = 5;
 
 /// Harvest rewards and auto-compound them back into the vault
 /// This can be called by anyone (permissionless) to incentivize compounding
-pub fn harvest(
-    ctx: Context<Harvest>,
+///
+/// Rewards already denominated in the underlying token are pulled from
+/// `rewards_token_account`. Rewards in other mints (RAY, ORCA, JTO, ...) are held
+/// in the vault's harvest rewards PDAs and passed via remaining accounts; they are
+/// swapped into the underlying mint before compounding. Trading fees accrued
+/// to the vault's CLMM position are collected into the vault token account.
+/// Liquid staking vaults realize the appreciation of their LST against SOL
+/// through the stake pool exchange rate, with no reward transfer needed.
+pub fn harvest<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Harvest<'info>>,
     rewards_amount: u64, // Amount of rewards harvested (in underlying token)
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
     
     // Check vault is not paused
-    require!(!vault.paused, VaultError::VaultPaused);
+    require!(!ctx.accounts.vault.paused, VaultError::VaultPaused);
     
     // Check harvest cooldown
     require!(
-        can_harvest(vault, clock.unix_timestamp)?,
+        can_harvest(&ctx.accounts.vault, clock.unix_timestamp)?,
         VaultError::HarvestNotReady
     );
     
-    if rewards_amount == 0 {
+    // Swap reward tokens of other mints into the underlying token
+    let rewards_swapped = swap_reward_tokens(&ctx, clock.unix_timestamp)?;
+    
+    // Collect CLMM trading fees (already in the underlying token)
+    let clmm_fees_collected = collect_clmm_fees(&ctx)?;
+    
+    // Transfer underlying-denominated rewards to vault (in production, this would come from yield source)
+    // For now, assume rewards are already in the rewards_token_account
+    // Swapped rewards and CLMM fees are already in the vault token account
+    let vault_token_info = ctx.accounts.vault_token_account.to_account_info();
+    let balance_before = accessor::amount(&vault_token_info)?;
+    if rewards_amount > 0 && ctx.accounts.rewards_token_account.amount >= rewards_amount {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.rewards_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: vault_token_info.clone(),
+            authority: ctx.accounts.rewards_authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, rewards_amount, ctx.accounts.underlying_mint.decimals)?;
+    }
+    
+    // Credit only what arrived (Token-2022 transfer fees are withheld from the recipient)
+    let rewards_received = accessor::amount(&vault_token_info)?
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let stake_pool_info = ctx.accounts.stake_pool.as_ref().map(|p| p.to_account_info());
+    let vault = &mut ctx.accounts.vault;
+    
+    // Mark liquid staking vaults to the current stake rate first, so token
+    // rewards below are valued at the same rate
+    let stake_yield = realize_stake_yield(vault, stake_pool_info.as_ref())?;
+    
+    let token_rewards = rewards_received
+        .checked_add(rewards_swapped)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(clmm_fees_collected)
+        .ok_or(VaultError::MathOverflow)?;
+    let total_rewards = underlying_to_assets(vault, token_rewards)?
+        .checked_add(stake_yield)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    if total_rewards == 0 {
         return Err(VaultError::HarvestNotReady.into());
     }
     
+    // Harvester bounty is taken from realized rewards before fees
+    let harvester_bounty = total_rewards
+        .checked_mul(vault.harvest_bounty_bps as u64)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let rewards_after_bounty = total_rewards
+        .checked_sub(harvester_bounty)
+        .ok_or(VaultError::MathOverflow)?;
+    
     // Calculate time elapsed since last harvest
     let time_elapsed = clock.unix_timestamp
         .checked_sub(vault.last_harvest)
@@ -67,31 +144,105 @@ pub fn harvest(
         .checked_add(performance_fee)
         .ok_or(VaultError::MathOverflow)?;
     
-    let rewards_after_fees = if rewards_amount > total_fees {
-        rewards_amount
+    let rewards_after_fees = if rewards_after_bounty > total_fees {
+        rewards_after_bounty
             .checked_sub(total_fees)
             .ok_or(VaultError::MathOverflow)?
     } else {
         0
     };
     
-    // Transfer rewards to vault (in production, this would come from yield source)
-    // For now, assume rewards are already in the rewards_token_account
-    if rewards_after_fees > 0 && ctx.accounts.rewards_token_account.amount >= rewards_after_fees {
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.rewards_token_account.to_account_info(),
-            to: ctx.accounts.vault_token_account.to_account_info(),
-            authority: ctx.accounts.rewards_authority.to_account_info(),
+    // Route a share of net rewards to the insurance reserve, if one exists
+    let reserve_contribution = if vault.reserve_token_account != Pubkey::default() {
+        rewards_after_fees
+            .checked_mul(vault.reserve_fee_bps as u64)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(VaultError::MathOverflow)?
+    } else {
+        0
+    };
+    
+    let rewards_reinvested = rewards_after_fees
+        .checked_sub(reserve_contribution)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Pay the harvester bounty out of the vault
+    if harvester_bounty > 0 {
+        let seeds = &[
+            b"vault".as_ref(),
+            &vault.vault_id.to_le_bytes(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: ctx.accounts.harvester_token_account.to_account_info(),
+            authority: vault_info.clone(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, rewards_after_fees)?;
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(
+            cpi_ctx,
+            assets_to_underlying(vault, harvester_bounty)?,
+            ctx.accounts.underlying_mint.decimals,
+        )?;
+    }
+    
+    // Move the reserve contribution out of the vault
+    if reserve_contribution > 0 {
+        let reserve_token_account = ctx.accounts.reserve_token_account
+            .as_ref()
+            .ok_or(VaultError::ReserveNotInitialized)?;
+    
+        let seeds = &[
+            b"vault".as_ref(),
+            &vault.vault_id.to_le_bytes(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: reserve_token_account.to_account_info(),
+            authority: vault_info,
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        let reserve_tokens = assets_to_underlying(vault, reserve_contribution)?;
+        token_interface::transfer_checked(cpi_ctx, reserve_tokens, ctx.accounts.underlying_mint.decimals)?;
+    
+        emit!(ReserveFundedEvent {
+            vault: vault.key(),
+            funder: ctx.accounts.harvester.key(),
+            amount: reserve_contribution,
+            reserve_balance: reserve_token_account.amount
+                .checked_add(reserve_tokens)
+                .ok_or(VaultError::MathOverflow)?,
+            timestamp: clock.unix_timestamp,
+        });
     }
     
     // Update vault state
     let assets_before = vault.total_assets;
+    
+    // Lockers' boosted share of the rewards is issued to them as new shares
+    let lock_boost = allocate_lock_boost(vault, rewards_reinvested, clock.unix_timestamp)?;
+    let rewards_compounded = rewards_reinvested
+        .checked_sub(lock_boost)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // New profit is locked and vests linearly, on top of whatever is still unvested
+    let still_locked = calculate_locked_profit(vault, clock.unix_timestamp)?;
+    vault.locked_profit = still_locked
+        .checked_add(rewards_compounded)
+        .ok_or(VaultError::MathOverflow)?;
+    
     vault.total_assets = vault.total_assets
-        .checked_add(rewards_after_fees)
+        .checked_add(rewards_compounded)
         .ok_or(VaultError::MathOverflow)?;
     vault.last_harvest = clock.unix_timestamp;
     
@@ -110,7 +261,7 @@ pub fn harvest(
     
     // Estimate APY
     let apy_estimate = estimate_apy(
-        rewards_after_fees,
+        rewards_reinvested,
         assets_before,
         time_elapsed.max(1),
     ).unwrap_or(0);
@@ -118,8 +269,14 @@ pub fn harvest(
     emit!(HarvestEvent {
         vault: vault.key(),
         harvester: ctx.accounts.harvester.key(),
-        rewards_harvested: rewards_amount,
-        rewards_reinvested: rewards_after_fees,
+        rewards_harvested: total_rewards,
+        rewards_swapped,
+        clmm_fees_collected,
+        stake_yield,
+        harvester_bounty,
+        reserve_contribution,
+        rewards_reinvested,
+        lock_boost,
         new_total_assets: vault.total_assets,
         apy_estimate,
         timestamp: clock.unix_timestamp,
@@ -136,14 +293,224 @@ pub fn harvest(
         });
     }
     
-    msg!("Harvested {} rewards, reinvested {} after fees", 
-         rewards_amount, rewards_after_fees);
+    msg!("Harvested {} rewards ({} from swaps, {} CLMM fees, {} stake yield), paid {} bounty, reserved {}, reinvested {} after fees", 
+         total_rewards, rewards_swapped, clmm_fees_collected, stake_yield, harvester_bounty, reserve_contribution, rewards_reinvested);
+    
+    Ok(())
+}
+
+/// Swap every harvest rewards account passed in remaining accounts into the
+/// underlying mint, bounding each swap by the oracle price minus the configured
+/// slippage. Returns the total underlying tokens received.
+fn swap_reward_tokens<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, Harvest<'info>>,
+    timestamp: i64,
+) -> Result<u64> {
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.is_empty() {
+        return Ok(0);
+    }
+    
+    require!(
+        remaining_accounts.len().is_multiple_of(REWARD_SWAP_ACCOUNTS),
+        VaultError::InvalidRewardAccounts
+    );
+    
+    let vault = &ctx.accounts.vault;
+    let config = &vault.strategy_config;
+    
+    // Validate swap program against strategy config
+    let swap_program = ctx.accounts.swap_program
+        .as_ref()
+        .ok_or(VaultError::InvalidSwapProgram)?;
+    require!(
+        config.swap_program == Some(swap_program.key()),
+        VaultError::InvalidSwapProgram
+    );
+    
+    // Underlying price is needed to bound every swap
+    let underlying_oracle = config.oracle_price_feed.ok_or(VaultError::InvalidOracle)?;
+    require!(
+        validate_oracle_price(&underlying_oracle, 300)?,
+        VaultError::StaleOraclePrice
+    );
+    let underlying_price = get_oracle_price(&underlying_oracle)?;
+    let underlying_decimals = ctx.accounts.underlying_mint.decimals;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let vault_token_info = ctx.accounts.vault_token_account.to_account_info();
+    let mut total_received: u64 = 0;
+    
+    for group in remaining_accounts.chunks(REWARD_SWAP_ACCOUNTS) {
+        let reward_token_account = InterfaceAccount::<TokenAccount>::try_from(&group[0])?;
+        let reward_mint = InterfaceAccount::<Mint>::try_from(&group[1])?;
+    
+        // Only the dedicated harvest rewards PDA may be swapped out of, never the
+        // emission reward vaults or a dual-asset vault's token B account
+        let (harvest_rewards_account, _) = Pubkey::find_program_address(
+            &[b"harvest_rewards", vault.key().as_ref(), reward_mint.key().as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            reward_token_account.key() == harvest_rewards_account
+                && reward_token_account.owner == vault.key(),
+            VaultError::InvalidRewardAccounts
+        );
+        require!(
+            reward_token_account.mint == reward_mint.key()
+                && reward_mint.key() != vault.underlying_mint,
+            VaultError::InvalidMint
+        );
+    
+        // Reward mint must be registered on the vault
+        let reward_config = config.reward_mints
+            .iter()
+            .find(|r| r.mint != Pubkey::default() && r.mint == reward_mint.key())
+            .ok_or(VaultError::InvalidMint)?;
+    
+        let amount_in = reward_token_account.amount;
+        if amount_in == 0 {
+            continue;
+        }
+    
+        require!(
+            validate_oracle_price(&reward_config.oracle_price_feed, 300)?,
+            VaultError::StaleOraclePrice
+        );
+        let reward_price = get_oracle_price(&reward_config.oracle_price_feed)?;
+    
+        let min_amount_out = calculate_min_swap_output(
+            amount_in,
+            reward_price,
+            reward_mint.decimals,
+            underlying_price,
+            underlying_decimals,
+            config.max_swap_slippage_bps,
+        )?;
+    
+        let balance_before = accessor::amount(&vault_token_info)?;
+    
+        swap(
+            &swap_program.to_account_info(),
+            SwapAccounts {
+                pool: group[2].clone(),
+                pool_source: group[3].clone(),
+                pool_destination: group[4].clone(),
+                source: group[0].clone(),
+                destination: vault_token_info.clone(),
+                authority: vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            amount_in,
+            min_amount_out,
+            signer,
+        )?;
+    
+        // Credit only what actually arrived in the vault
+        let balance_after = accessor::amount(&vault_token_info)?;
+        let amount_out = balance_after
+            .checked_sub(balance_before)
+            .ok_or(VaultError::MathOverflow)?;
+    
+        require!(amount_out >= min_amount_out, VaultError::SlippageExceeded);
+    
+        total_received = total_received
+            .checked_add(amount_out)
+            .ok_or(VaultError::MathOverflow)?;
+    
+        emit!(RewardSwapEvent {
+            vault: vault.key(),
+            reward_mint: reward_mint.key(),
+            amount_in,
+            amount_out,
+            min_amount_out,
+            timestamp,
+        });
+    }
+    
+    Ok(total_received)
+}
+
+/// Collect trading fees accrued to the vault's CLMM position into the vault
+/// token account. Returns the fees collected (0 if the vault has no position).
+fn collect_clmm_fees<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, Harvest<'info>>,
+) -> Result<u64> {
+    let vault = &ctx.accounts.vault;
+    let position_key = match vault.strategy_config.clmm_position {
+        Some(position_key) => position_key,
+        None => return Ok(0),
+    };
+    
+    let clmm_program = ctx.accounts.clmm_program
+        .as_ref()
+        .ok_or(VaultError::InvalidClmmAccount)?;
+    let pool = ctx.accounts.clmm_pool
+        .as_ref()
+        .ok_or(VaultError::InvalidClmmAccount)?;
+    let position = ctx.accounts.clmm_position
+        .as_ref()
+        .ok_or(VaultError::InvalidClmmAccount)?;
+    let pool_token_vault = ctx.accounts.clmm_pool_token_vault
+        .as_ref()
+        .ok_or(VaultError::InvalidClmmAccount)?;
+    
+    validate_clmm_accounts(vault, &clmm_program.key(), &pool.key())?;
+    require!(position.key() == position_key, VaultError::InvalidClmmAccount);
+    
+    let fees_owed = read_position(&position.to_account_info(), &clmm_program.key())?.fees_owed;
+    if fees_owed == 0 {
+        return Ok(0);
+    }
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let vault_token_info = ctx.accounts.vault_token_account.to_account_info();
+    let balance_before = accessor::amount(&vault_token_info)?;
+    
+    clmm::collect_fees(
+        &clmm_program.to_account_info(),
+        ClmmLiquidityAccounts {
+            pool: pool.to_account_info(),
+            position: position.to_account_info(),
+            token_vault: pool_token_vault.to_account_info(),
+            token_account: ctx.accounts.vault_token_account.to_account_info(),
+            owner: vault.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        signer,
+    )?;
+    
+    // Credit what the pool actually paid out, not the position's reported fees
+    accessor::amount(&vault_token_info)?
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Create the vault's harvest rewards account for a reward mint (authority only)
+/// Farms pay non-underlying rewards here; harvest swaps them into the underlying
+pub fn initialize_harvest_rewards_account(ctx: Context<InitializeHarvestRewardsAccount>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    msg!("Harvest rewards account {} created for reward mint {}",
+         ctx.accounts.harvest_rewards_account.key(), ctx.accounts.reward_mint.key());
     
     Ok(())
 }
 
 /// Collect accrued fees to treasury
 pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
     
@@ -163,20 +530,25 @@ pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
     
     // Transfer fees to treasury
     let seeds = &[
-        b"vault",
+        b"vault".as_ref(),
         &vault.vault_id.to_le_bytes(),
         &[vault.bump],
     ];
     let signer = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_token_account.to_account_info(),
+        mint: ctx.accounts.underlying_mint.to_account_info(),
         to: ctx.accounts.treasury_token_account.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
+        authority: vault_info,
     };
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, total_fees)?;
+    token_interface::transfer_checked(
+        cpi_ctx,
+        assets_to_underlying(vault, total_fees)?,
+        ctx.accounts.underlying_mint.decimals,
+    )?;
     
     emit!(FeeCollectionEvent {
         vault: vault.key(),
@@ -216,18 +588,97 @@ pub struct Harvest<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: Rewards token account (source of rewards)
-    #[account(mut)]
-    pub rewards_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: Rewards token account (source of underlying-denominated rewards)
+    #[account(
+        mut,
+        constraint = rewards_token_account.mint == vault.underlying_mint
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Authority that can transfer from rewards account
     pub rewards_authority: UncheckedAccount<'info>,
     
     pub harvester: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        constraint = harvester_token_account.mint == vault.underlying_mint,
+        constraint = harvester_token_account.owner == harvester.key()
+    )]
+    pub harvester_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Insurance reserve (required when the vault has a reserve with a non-zero harvest cut)
+    #[account(
+        mut,
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Swap adapter program, validated against strategy_config.swap_program
+    /// (only required when reward token swaps are passed in remaining accounts)
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: CLMM adapter program, validated against strategy_config.clmm_program
+    /// (CLMM accounts are only required when the vault has a CLMM position)
+    pub clmm_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: CLMM pool, validated against strategy_config.pool_address
+    pub clmm_pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Vault's CLMM position, validated against strategy_config.clmm_position
+    #[account(mut)]
+    pub clmm_position: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool token vault fees are paid from, validated by the CLMM program
+    #[account(mut)]
+    pub clmm_pool_token_vault: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: SPL stake pool, validated against strategy_config.stake_pool
+    /// (only required for liquid staking vaults)
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHarvestRewardsAccount<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = reward_mint.key() != vault.underlying_mint @ VaultError::InvalidMint,
+        mint::token_program = token_program
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Vault-owned token account receiving this mint's farm rewards
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"harvest_rewards", vault.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub harvest_rewards_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -250,13 +701,18 @@ pub struct CollectFees<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Treasury token account
     #[account(mut)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
     
     pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
@@ -0,0 +1,350 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::adapters::perp::{
+    self, read_position, InitPerpPositionAccounts, PerpMarginAccounts, PerpOrderAccounts,
+};
+use crate::utils::{
+    calculate_net_delta, calculate_perp_limit_price, calculate_spot_exposure, get_oracle_price,
+    should_rehedge, validate_oracle_price,
+};
+use crate::events::RehedgeEvent;
+
+/// Open the vault's perp hedge position and post initial margin (delta-neutral only)
+/// Margin is drawn from the vault token account and stays part of total assets
+pub fn initialize_hedge(
+    ctx: Context<InitializeHedge>,
+    margin_amount: u64,
+) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can open the hedge
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(vault.strategy == 3, VaultError::InvalidStrategy);
+    require!(
+        vault.strategy_config.hedge_position.is_none(),
+        VaultError::InvalidHedgeAccount
+    );
+    validate_perp_accounts(
+        vault,
+        &ctx.accounts.perp_program.key(),
+        &ctx.accounts.perp_market.key(),
+    )?;
+    
+    let perp_program = ctx.accounts.perp_program.to_account_info();
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    perp::init_position(
+        &perp_program,
+        InitPerpPositionAccounts {
+            market: ctx.accounts.perp_market.to_account_info(),
+            position: ctx.accounts.perp_position.to_account_info(),
+            owner: vault_info.clone(),
+            payer: ctx.accounts.authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        signer,
+    )?;
+    
+    if margin_amount > 0 {
+        perp::deposit_margin(
+            &perp_program,
+            PerpMarginAccounts {
+                market: ctx.accounts.perp_market.to_account_info(),
+                position: ctx.accounts.perp_position.to_account_info(),
+                margin_vault: ctx.accounts.margin_vault.to_account_info(),
+                token_account: ctx.accounts.vault_token_account.to_account_info(),
+                owner: vault_info,
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            margin_amount,
+            signer,
+        )?;
+    }
+    
+    // Idle cash and posted margin carry no spot exposure
+    ctx.accounts.vault_token_account.reload()?;
+    let spot_exposure = calculate_spot_exposure(
+        vault.total_assets,
+        ctx.accounts.vault_token_account.amount,
+        margin_amount,
+    );
+    
+    vault.strategy_config.hedge_position = Some(ctx.accounts.perp_position.key());
+    vault.hedge_size = 0;
+    vault.net_delta = calculate_net_delta(spot_exposure, 0)?;
+    
+    msg!("Opened hedge position for vault {} with {} margin", 
+         vault.vault_id, margin_amount);
+    
+    Ok(())
+}
+
+/// Post additional margin from the vault token account to the hedge position
+pub fn deposit_hedge_margin(
+    ctx: Context<DepositHedgeMargin>,
+    amount: u64,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    // Only vault authority can move margin
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(amount > 0, VaultError::InsufficientFunds);
+    validate_perp_accounts(
+        vault,
+        &ctx.accounts.perp_program.key(),
+        &ctx.accounts.perp_market.key(),
+    )?;
+    require!(
+        Some(ctx.accounts.perp_position.key()) == vault.strategy_config.hedge_position,
+        VaultError::InvalidHedgeAccount
+    );
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    perp::deposit_margin(
+        &ctx.accounts.perp_program.to_account_info(),
+        PerpMarginAccounts {
+            market: ctx.accounts.perp_market.to_account_info(),
+            position: ctx.accounts.perp_position.to_account_info(),
+            margin_vault: ctx.accounts.margin_vault.to_account_info(),
+            token_account: ctx.accounts.vault_token_account.to_account_info(),
+            owner: vault.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        amount,
+        signer,
+    )?;
+    
+    msg!("Posted {} hedge margin for vault {}", amount, vault.vault_id);
+    
+    Ok(())
+}
+
+/// Resize the perp hedge so it offsets the vault's spot exposure
+/// Permissionless: only succeeds when |net delta| exceeds the rehedge band, and
+/// the order is limited to the oracle price within `max_swap_slippage_bps`
+pub fn rehedge(ctx: Context<Rehedge>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    require!(vault.strategy == 3, VaultError::InvalidStrategy);
+    require!(!vault.paused, VaultError::VaultPaused);
+    validate_perp_accounts(
+        vault,
+        &ctx.accounts.perp_program.key(),
+        &ctx.accounts.perp_market.key(),
+    )?;
+    require!(
+        Some(ctx.accounts.perp_position.key()) == vault.strategy_config.hedge_position,
+        VaultError::InvalidHedgeAccount
+    );
+    
+    // The perp position is the source of truth for the hedge size
+    let position = read_position(
+        &ctx.accounts.perp_position.to_account_info(),
+        &ctx.accounts.perp_program.key(),
+    )?;
+    require!(
+        position.owner == vault.key() && position.market == ctx.accounts.perp_market.key(),
+        VaultError::InvalidHedgeAccount
+    );
+    
+    // Exposure comes from the spot leg only; idle cash and posted margin are excluded
+    let spot_exposure = calculate_spot_exposure(
+        vault.total_assets,
+        ctx.accounts.vault_token_account.amount,
+        position.margin,
+    );
+    let hedge_size_before = position.base_size;
+    let net_delta_before = calculate_net_delta(spot_exposure, hedge_size_before)?;
+    
+    let band_bps = vault.strategy_config
+        .rehedge_band_bps
+        .unwrap_or(200); // 2% default
+    require!(
+        should_rehedge(net_delta_before, spot_exposure, band_bps)?,
+        VaultError::RehedgeNotNeeded
+    );
+    
+    // Trade the net delta away: sell when long, buy back when over-hedged
+    let base_delta = net_delta_before
+        .checked_neg()
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Bound the fill by the oracle so a keeper cannot trade the hedge at any price
+    let oracle = vault.strategy_config
+        .oracle_price_feed
+        .ok_or(VaultError::InvalidOracle)?;
+    require!(validate_oracle_price(&oracle, 300)?, VaultError::StaleOraclePrice);
+    let limit_price = calculate_perp_limit_price(
+        get_oracle_price(&oracle)?,
+        base_delta,
+        vault.strategy_config.max_swap_slippage_bps,
+    )?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    perp::place_order(
+        &ctx.accounts.perp_program.to_account_info(),
+        PerpOrderAccounts {
+            market: ctx.accounts.perp_market.to_account_info(),
+            position: ctx.accounts.perp_position.to_account_info(),
+            owner: vault_info,
+        },
+        base_delta,
+        limit_price,
+        signer,
+    )?;
+    
+    vault.hedge_size = hedge_size_before
+        .checked_add(base_delta)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.net_delta = calculate_net_delta(spot_exposure, vault.hedge_size)?;
+    
+    emit!(RehedgeEvent {
+        vault: vault.key(),
+        keeper: ctx.accounts.keeper.key(),
+        spot_exposure,
+        hedge_size_before,
+        hedge_size_after: vault.hedge_size,
+        net_delta_before,
+        net_delta_after: vault.net_delta,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Rehedged vault {}: hedge {} -> {}", 
+         vault.vault_id, hedge_size_before, vault.hedge_size);
+    
+    Ok(())
+}
+
+/// Check the perp program and market against the vault's strategy config
+fn validate_perp_accounts(
+    vault: &Vault,
+    perp_program: &Pubkey,
+    perp_market: &Pubkey,
+) -> Result<()> {
+    require!(
+        Some(*perp_program) == vault.strategy_config.perp_program,
+        VaultError::InvalidHedgeAccount
+    );
+    require!(
+        Some(*perp_market) == vault.strategy_config.perp_market,
+        VaultError::InvalidHedgeAccount
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeHedge<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Perp adapter program, validated against the strategy config
+    pub perp_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Perp market, validated against the strategy config
+    pub perp_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Perp position to open, created and validated by the perp program
+    #[account(mut)]
+    pub perp_position: UncheckedAccount<'info>,
+    
+    /// CHECK: Market margin token account, validated by the perp program
+    #[account(mut)]
+    pub margin_vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositHedgeMargin<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Perp adapter program, validated against the strategy config
+    pub perp_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Perp market, validated against the strategy config
+    pub perp_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault's hedge position, validated against the strategy config
+    #[account(mut)]
+    pub perp_position: UncheckedAccount<'info>,
+    
+    /// CHECK: Market margin token account, validated by the perp program
+    #[account(mut)]
+    pub margin_vault: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct Rehedge<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// Idle vault liquidity, excluded from the hedged spot exposure
+    #[account(
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Perp adapter program, validated against the strategy config
+    pub perp_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Perp market, validated against the strategy config
+    pub perp_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault's hedge position, validated against the strategy config and read in handler
+    #[account(mut)]
+    pub perp_position: UncheckedAccount<'info>,
+    
+    pub keeper: Signer<'info>,
+}
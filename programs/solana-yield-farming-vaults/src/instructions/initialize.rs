@@ -1,7 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{self, TokenMetadataInitialize};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::errors::VaultError;
+use crate::events::*;
+use crate::instructions::share_metadata::{fund_share_mint_rent, validate_share_metadata};
+use crate::instructions::rewards::{reward_hook_extra_account_metas, REWARD_HOOK_EXTRA_ACCOUNTS};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_metadata_interface::state::TokenMetadata;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
 
 /// Initialize the global protocol state
 pub fn initialize_global_state(
@@ -36,6 +45,7 @@ pub fn initialize_global_state(
 }
 
 /// Initialize a new vault
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_vault(
     ctx: Context<InitializeVault>,
     strategy: u8,
@@ -43,21 +53,24 @@ pub fn initialize_vault(
     performance_fee_bps: Option<u16>,
     max_leverage_bps: Option<u16>,
     min_deposit: u64,
+    share_name: String,
+    share_symbol: String,
+    share_uri: String,
 ) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
     let vault = &mut ctx.accounts.vault;
     let clock = Clock::get()?;
     
-    // Validate strategy type (0-3)
+    // Validate strategy type (0-4)
     require!(
-        strategy <= 3,
+        strategy <= 4,
         VaultError::InvalidStrategy
     );
     
     // Validate leverage (max 5x = 50000 bps)
     if let Some(leverage) = max_leverage_bps {
         require!(
-            leverage <= 50000 && leverage >= 10000,
+            (10000..=50000).contains(&leverage),
             VaultError::InvalidLeverage
         );
     }
@@ -71,6 +84,8 @@ pub fn initialize_vault(
         VaultError::InvalidFeeConfig
     );
     
+    validate_share_metadata(Some(&share_name), Some(&share_symbol), Some(&share_uri))?;
+    
     // Initialize vault
     vault.vault_id = global_state.vault_count;
     vault.strategy = strategy;
@@ -94,9 +109,78 @@ pub fn initialize_vault(
     vault.rebalance_threshold_bps = 500; // 5% deviation threshold
     vault.harvest_cooldown = 3600; // 1 hour default
     vault.rebalance_cooldown = 86400; // 24 hours default
+    vault.harvest_bounty_bps = 0; // No harvester bounty by default
+    vault.locked_profit = 0;
+    vault.profit_unlock_duration = 21600; // 6 hours default
+    vault.deposits_paused = false;
+    vault.max_loss_report_bps = 1000; // 10% of assets per report
+    vault.loss_pause_threshold_bps = 500; // Pause deposits after a 5% socialized loss
+    vault.reserve_token_account = Pubkey::default(); // No reserve until initialize_reserve
+    vault.reserve_fee_bps = 0;
+    vault.pending_reserve_withdrawal = 0;
+    vault.reserve_withdrawal_unlock_ts = 0;
+    vault.total_collateral = 0;
+    vault.total_debt = 0;
+    vault.hedge_size = 0;
+    vault.net_delta = 0;
+    vault.token_b_mint = Pubkey::default();
+    vault.vault_token_b_account = Pubkey::default();
+    vault.total_assets_b = 0;
+    vault.lst_exchange_rate = 0; // Set by initialize_stake_pool for liquid staking
+    vault.lock_boost_bps = 0; // Set by initialize_locks
+    vault.early_unlock_penalty_bps = 0;
+    vault.total_locked_shares = 0;
+    vault.total_lock_weight = 0;
+    vault.acc_boost_per_weight = 0;
     vault.strategy_config = StrategyConfig::default();
     vault.bump = ctx.bumps.vault;
     
+    // Write share token metadata into the mint (Token-2022 metadata extension)
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    // Fund the metadata entry before Token-2022 grows the mint for it
+    let metadata = TokenMetadata {
+        update_authority: Some(vault.key()).try_into()?,
+        mint: ctx.accounts.share_mint.key(),
+        name: share_name.clone(),
+        symbol: share_symbol.clone(),
+        uri: share_uri.clone(),
+        additional_metadata: vec![],
+    };
+    fund_share_mint_rent(
+        &ctx.accounts.share_mint.to_account_info(),
+        &metadata,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    let cpi_accounts = TokenMetadataInitialize {
+        token_program_id: ctx.accounts.share_token_program.to_account_info(),
+        metadata: ctx.accounts.share_mint.to_account_info(),
+        update_authority: vault.to_account_info(),
+        mint_authority: vault.to_account_info(),
+        mint: ctx.accounts.share_mint.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.share_token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_2022_extensions::token_metadata_initialize(
+        cpi_ctx,
+        share_name,
+        share_symbol,
+        share_uri,
+    )?;
+    
+    // Resolve the reward accounts share transfers must pass to the hook
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+        &reward_hook_extra_account_metas(&vault.key())?,
+    )?;
+    
     // Increment vault count
     global_state.vault_count = global_state.vault_count
         .checked_add(1)
@@ -153,17 +237,62 @@ pub struct InitializeVault<'info> {
     )]
     pub global_state: Account<'info, GlobalState>,
     
-    /// CHECK: Underlying token mint
-    pub underlying_mint: Account<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Share token mint, a vault-derived PDA with the vault as mint authority
+    /// Carries its own name/symbol/URI through the metadata pointer extension,
+    /// and calls this program's transfer hook to checkpoint emission rewards
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"share_mint", vault.key().as_ref()],
+        bump,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = vault,
+        mint::token_program = share_token_program,
+        extensions::metadata_pointer::authority = vault,
+        extensions::metadata_pointer::metadata_address = share_mint,
+        extensions::transfer_hook::authority = vault,
+        extensions::transfer_hook::program_id = crate::ID
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
     
-    /// CHECK: Share token mint (should be initialized separately)
-    pub share_mint: Account<'info, Mint>,
+    /// CHECK: Extra account metas of the share mint's transfer hook, written in handler
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(REWARD_HOOK_EXTRA_ACCOUNTS)?,
+        seeds = [b"extra-account-metas", share_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
     
-    /// CHECK: Vault's token account (should be initialized separately)
-    pub vault_token_account: Account<'info, TokenAccount>,
+    /// Vault's underlying token account (ATA owned by the vault)
+    /// Anyone can create the vault's ATA ahead of time, so an existing one is
+    /// accepted as long as its mint and owner match
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+        constraint = vault_token_account.mint == underlying_mint.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    /// Token program of the underlying mint (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token-2022, which owns the share mint and its metadata
+    pub share_token_program: Program<'info, Token2022>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
     pub system_program: Program<'info, System>,
 }
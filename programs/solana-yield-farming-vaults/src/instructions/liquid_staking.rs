@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::adapters::stake_pool::read_stake_pool;
+use crate::utils::{assets_to_underlying, calculate_stake_rate, underlying_to_assets};
+
+/// Attach the stake pool of a liquid staking vault's LST (jitoSOL, ...)
+/// From then on total_assets is accounted in SOL lamports, marked through
+/// the pool's exchange rate, and harvest realizes stake-rate appreciation
+pub fn initialize_stake_pool(ctx: Context<InitializeStakePool>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can attach the stake pool
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(vault.strategy == 4, VaultError::InvalidStrategy);
+    require!(vault.total_shares == 0, VaultError::InvalidStrategy);
+    
+    let pool = read_stake_pool(&ctx.accounts.stake_pool.to_account_info())?;
+    require!(
+        pool.pool_mint == vault.underlying_mint,
+        VaultError::InvalidStakePool
+    );
+    
+    vault.lst_exchange_rate = calculate_stake_rate(pool.total_lamports, pool.pool_token_supply)?;
+    vault.strategy_config.stake_pool = Some(ctx.accounts.stake_pool.key());
+    
+    msg!("Vault {} marked to SOL via stake pool {} at rate {}",
+         vault.vault_id, ctx.accounts.stake_pool.key(), vault.lst_exchange_rate);
+    
+    Ok(())
+}
+
+/// Mark a liquid staking vault to the stake pool's current exchange rate
+/// Returns the SOL-denominated appreciation of the LST backing total_assets
+/// since the last harvest (0 for other vaults). Rate decreases (slashing) are
+/// left to `report_loss` rather than marked down here.
+pub fn realize_stake_yield(
+    vault: &mut Account<Vault>,
+    stake_pool: Option<&AccountInfo>,
+) -> Result<u64> {
+    let stake_pool_key = match vault.strategy_config.stake_pool {
+        Some(stake_pool_key) => stake_pool_key,
+        None => return Ok(0),
+    };
+    
+    let stake_pool = stake_pool.ok_or(VaultError::InvalidStakePool)?;
+    require!(stake_pool.key() == stake_pool_key, VaultError::InvalidStakePool);
+    
+    let pool = read_stake_pool(stake_pool)?;
+    let new_rate = calculate_stake_rate(pool.total_lamports, pool.pool_token_supply)?;
+    if new_rate <= vault.lst_exchange_rate {
+        return Ok(0);
+    }
+    
+    // LST held for depositors, priced at the old and the new rate
+    let lst_backing = assets_to_underlying(vault, vault.total_assets)?;
+    vault.lst_exchange_rate = new_rate;
+    let marked_assets = underlying_to_assets(vault, lst_backing)?;
+    
+    Ok(marked_assets.saturating_sub(vault.total_assets))
+}
+
+#[derive(Accounts)]
+pub struct InitializeStakePool<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// CHECK: SPL stake pool, validated by owner and pool mint in handler
+    pub stake_pool: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
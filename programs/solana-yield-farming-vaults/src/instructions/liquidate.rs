@@ -1,117 +1,308 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::accessor;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::VaultError;
-use crate::utils::{calculate_health_factor, validate_oracle_price, get_oracle_price};
-use crate::events::{LiquidationEvent, LeverageAdjustmentEvent};
+use crate::adapters::lending::{self, read_obligation, LendingAccounts};
+use crate::adapters::{swap, SwapAccounts};
+use crate::utils::{
+    calculate_debt, calculate_leveraged_position, calculate_position_health_factor,
+    calculate_auction_bonus, calculate_leverage_bps, convert_at_oracle_price, get_position_prices,
+    calculate_min_swap_output, calculate_max_swap_input,
+};
+use crate::events::{
+    LiquidationEvent, LiquidationAuctionStartedEvent, LiquidationAuctionFilledEvent,
+    LiquidationAuctionCancelledEvent,
+    LeverageAdjustmentEvent, BadDebtEvent, VaultHealthEvent, AutoDeleverageEvent,
+};
+use super::reserve::cover_from_reserve;
 
 /// Liquidate an undercollateralized leveraged position
-/// This is called when health factor drops below liquidation threshold
-pub fn liquidate(
-    ctx: Context<Liquidate>,
-    collateral_to_seize: u64,
+/// This is called when health factor drops below liquidation threshold.
+/// The liquidator repays part of the position's debt in the debt token and
+/// receives collateral worth the repaid debt plus the liquidation bonus,
+/// withdrawn from the position's lending obligation
+pub fn liquidate<'info>(
+    ctx: Context<'_, '_, '_, 'info, Liquidate<'info>>,
+    mut debt_to_repay: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let user_position = &ctx.accounts.user_position;
     let clock = Clock::get()?;
     
-    // Check vault is not paused
-    require!(!vault.paused, VaultError::VaultPaused);
-    
-    // Validate oracle
-    if let Some(oracle) = vault.strategy_config.oracle_price_feed {
-        let is_valid = validate_oracle_price(&oracle, 300)?;
-        require!(is_valid, VaultError::StaleOraclePrice);
-    }
+    let lending_program = ctx.accounts.lending_program.to_account_info();
+    let debt_leg = get_debt_leg(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.liquidity_supply,
+        &ctx.accounts.vault_debt_token_account,
+        &ctx.accounts.debt_liquidity_supply,
+    )?;
+    let liquidator_debt_token_account = match &ctx.accounts.liquidator_debt_token_account {
+        Some(account) => account.to_account_info(),
+        None => {
+            require!(!is_cross_asset_debt(&ctx.accounts.vault), VaultError::InvalidMint);
+            ctx.accounts.liquidator_token_account.to_account_info()
+        }
+    };
+    let lending_accounts = |token_account: AccountInfo<'info>, liquidity_supply: AccountInfo<'info>| LendingAccounts {
+        market: ctx.accounts.lending_market.to_account_info(),
+        obligation: ctx.accounts.obligation.to_account_info(),
+        liquidity_supply,
+        token_account,
+        owner: ctx.accounts.vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let collateral_accounts = || lending_accounts(
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.liquidity_supply.to_account_info(),
+    );
+    let debt_accounts = || lending_accounts(
+        debt_leg.token_account.clone(),
+        debt_leg.liquidity_supply.clone(),
+    );
     
-    // Calculate health factor
-    let collateral_factor = vault.strategy_config
-        .collateral_factor_bps
-        .unwrap_or(8000); // 80% default
+    let vault = &ctx.accounts.vault;
+    let user_position = &ctx.accounts.user_position;
     
-    let health_factor = calculate_health_factor(
-        user_position.collateral,
-        user_position.debt,
-        collateral_factor,
-    )?;
+    // Check vault is not paused
+    require!(!vault.paused, VaultError::VaultPaused);
     
-    let liquidation_threshold = vault.strategy_config
-        .liquidation_threshold_bps
-        .unwrap_or(11000); // 1.1x = 11000 bps
+    // Value collateral and debt legs at validated oracle prices
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    let health_factor = prices.health_factor(vault, user_position.collateral, user_position.debt)?;
     
     require!(
-        health_factor < liquidation_threshold,
+        health_factor < get_liquidation_threshold(vault),
         VaultError::LiquidationNotNeeded
     );
     
-    // Validate collateral to seize
-    require!(
-        collateral_to_seize <= user_position.collateral,
-        VaultError::InsufficientFunds
-    );
+    require!(debt_to_repay > 0, VaultError::InsufficientFunds);
+    
+    // Close factor: at most this share of the debt can be repaid per call
+    let close_factor = vault.strategy_config
+        .close_factor_bps
+        .unwrap_or(5000); // 50% default
+    let max_repay = (user_position.debt as u128)
+        .checked_mul(close_factor as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)? as u64;
+    
+    require!(debt_to_repay <= max_repay, VaultError::CloseFactorExceeded);
+    
+    // Collateral seized is worth the repaid debt plus the liquidation bonus.
+    // In auction mode the bonus grows linearly from the auction start, and an
+    // auction left unfilled past its duration must be restarted
+    let auction_start = user_position.liquidation_auction_start;
+    let liquidation_bonus = match vault.strategy_config.auction_max_bonus_bps {
+        Some(max_bonus) => {
+            require!(auction_start > 0, VaultError::AuctionNotStarted);
+            require!(
+                !is_auction_expired(vault, auction_start, clock.unix_timestamp),
+                VaultError::AuctionExpired
+            );
+            calculate_auction_bonus(
+                auction_start,
+                clock.unix_timestamp,
+                max_bonus,
+                get_auction_duration(vault),
+            )?
+        }
+        None => vault.strategy_config
+            .liquidation_bonus_bps
+            .unwrap_or(10500), // 5% bonus default
+    };
+    let protocol_fee_share = vault.strategy_config
+        .liquidation_protocol_fee_bps
+        .unwrap_or(0); // No protocol cut by default
     
-    // Calculate debt to repay (with liquidation bonus)
-    // Liquidation bonus: 5% (10500 bps)
-    const LIQUIDATION_BONUS_BPS: u16 = 10500;
-    let debt_to_repay = collateral_to_seize
-        .checked_mul(LIQUIDATION_BONUS_BPS as u64)
+    let mut repaid_in_collateral = prices.debt_in_collateral(debt_to_repay)?;
+    let mut collateral_to_seize = repaid_in_collateral
+        .checked_mul(liquidation_bonus as u64)
         .ok_or(VaultError::MathOverflow)?
         .checked_div(10000)
         .ok_or(VaultError::MathOverflow)?;
     
-    require!(
-        debt_to_repay <= user_position.debt,
-        VaultError::MathOverflow
-    );
+    // Collateral left in the obligation caps the seizure; the repayment shrinks to match
+    let obligation_before = read_obligation(
+        &ctx.accounts.obligation.to_account_info(),
+        &lending_program.key(),
+    )?;
+    
+    if collateral_to_seize > obligation_before.deposited_amount {
+        collateral_to_seize = obligation_before.deposited_amount;
+        repaid_in_collateral = collateral_to_seize
+            .checked_mul(10000)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(liquidation_bonus as u64)
+            .ok_or(VaultError::MathOverflow)?;
+        debt_to_repay = prices.collateral_in_debt(repaid_in_collateral)?;
+    }
     
-    // Transfer collateral from vault to liquidator
-    let seeds = &[
-        b"vault",
-        &vault.vault_id.to_le_bytes(),
-        &[vault.bump],
-    ];
+    // Protocol takes its cut from the bonus portion only
+    let bonus_amount = collateral_to_seize
+        .checked_sub(repaid_in_collateral)
+        .ok_or(VaultError::MathOverflow)?;
+    let protocol_fee = bonus_amount
+        .checked_mul(protocol_fee_share as u64)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    let liquidator_collateral = collateral_to_seize
+        .checked_sub(protocol_fee)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
     let signer = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.vault_token_account.to_account_info(),
-        to: ctx.accounts.liquidator_token_account.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, collateral_to_seize)?;
+    if debt_to_repay > 0 {
+        // Liquidator pays the debt into the vault, which repays the obligation
+        let cpi_accounts = TransferChecked {
+            from: liquidator_debt_token_account,
+            mint: get_debt_mint_info(&ctx.accounts.underlying_mint, &ctx.accounts.debt_mint),
+            to: debt_leg.token_account.clone(),
+            authority: ctx.accounts.liquidator.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, debt_to_repay, prices.debt_decimals)?;
     
-    // In production, liquidator would repay debt to lending protocol
-    // Note: UserPosition would need to be updated separately in production
+        lending::repay(&lending_program, debt_accounts(), debt_to_repay, signer)?;
+    }
     
-    // Update vault state
-    vault.total_assets = vault.total_assets
-        .checked_sub(collateral_to_seize)
-        .ok_or(VaultError::MathOverflow)?;
+    // Bad debt: seizing the rest of the collateral leaves debt nothing backs.
+    // The vault owns the obligation, so it repays the residual itself before the
+    // collateral is released - drawing the insurance reserve first and
+    // socializing the rest across vault shares
+    let mut bad_debt = 0;
+    let mut reserve_covered = 0;
+    let mut socialized_loss = 0;
     
-    // Update leverage
-    let new_collateral = user_position.collateral
-        .checked_sub(collateral_to_seize)
-        .ok_or(VaultError::MathOverflow)?;
+    if collateral_to_seize == obligation_before.deposited_amount {
+        let obligation = read_obligation(
+            &ctx.accounts.obligation.to_account_info(),
+            &lending_program.key(),
+        )?;
+        bad_debt = obligation.borrowed_amount;
+    }
     
-    let new_debt = user_position.debt
-        .checked_sub(debt_to_repay)
-        .ok_or(VaultError::MathOverflow)?;
+    if bad_debt > 0 {
+        // Cross-asset debt is bought with underlying at up to the vault's swap slippage
+        let cross_asset = is_cross_asset_debt(&ctx.accounts.vault);
+        let bad_debt_cost = if cross_asset {
+            prices.collateral_for_debt(bad_debt, ctx.accounts.vault.strategy_config.max_swap_slippage_bps)?
+        } else {
+            prices.debt_in_collateral(bad_debt)?
+        };
     
-    let new_leverage = if new_collateral > 0 {
-        let position = new_collateral
-            .checked_add(new_debt)
-            .ok_or(VaultError::MathOverflow)?;
-        position
-            .checked_mul(10000)
-            .ok_or(VaultError::MathOverflow)?
-            .checked_div(new_collateral)
-            .unwrap_or(10000)
+        if let Some(reserve_token_account) = &ctx.accounts.reserve_token_account {
+            reserve_covered = cover_from_reserve(
+                &ctx.accounts.vault,
+                reserve_token_account,
+                &ctx.accounts.vault_token_account,
+                &ctx.accounts.underlying_mint,
+                &ctx.accounts.token_program,
+                bad_debt_cost,
+            )?;
+        }
+        socialized_loss = bad_debt_cost.saturating_sub(reserve_covered);
+    
+        // Residual debt is repaid from the vault's idle underlying liquidity,
+        // topped up by the reserve above
+        if cross_asset {
+            let leg_swap = get_leg_swap(
+                &ctx.accounts.vault,
+                &ctx.accounts.token_program,
+                &ctx.accounts.swap_program,
+                &ctx.accounts.swap_pool,
+                &ctx.accounts.swap_pool_source,
+                &ctx.accounts.swap_pool_destination,
+            )?;
+            leg_swap.swap(
+                ctx.accounts.vault_token_account.to_account_info(),
+                debt_leg.token_account.clone(),
+                bad_debt_cost,
+                bad_debt,
+                signer,
+            )?;
+        }
+        lending::repay(&lending_program, debt_accounts(), bad_debt, signer)?;
+    }
+    
+    if collateral_to_seize > 0 {
+        // Withdraw the seized collateral from the obligation and pay the liquidator
+        lending::withdraw_collateral(&lending_program, collateral_accounts(), collateral_to_seize, signer)?;
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: ctx.accounts.liquidator_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, liquidator_collateral, ctx.accounts.underlying_mint.decimals)?;
+    }
+    
+    // Protocol cut of the bonus goes to the treasury
+    if protocol_fee > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, protocol_fee, ctx.accounts.underlying_mint.decimals)?;
+    }
+    
+    // Recorded debt mirrors the obligation on the lending side
+    let obligation = read_obligation(
+        &ctx.accounts.obligation.to_account_info(),
+        &lending_program.key(),
+    )?;
+    
+    // Position equity: supplied collateral loses the seized amount while debt
+    // shrinks by the repaid amount (valued in collateral units). Written-off
+    // positions are closed out
+    let new_collateral = if bad_debt > 0 {
+        0
     } else {
-        10000 // 1x
+        user_position.collateral
+            .checked_add(repaid_in_collateral)
+            .ok_or(VaultError::MathOverflow)?
+            .saturating_sub(collateral_to_seize)
     };
     
-    vault.current_leverage_bps = new_leverage.min(vault.max_leverage_bps) as u16;
+    let new_debt = obligation.borrowed_amount;
+    let new_leverage = calculate_leverage_bps(new_collateral, prices.debt_in_collateral(new_debt)?)?;
+    let collateral_before = user_position.collateral;
+    let debt_before = user_position.debt;
+    
+    let vault = &mut ctx.accounts.vault;
+    let user_position = &mut ctx.accounts.user_position;
+    
+    // Seized collateral was the position's own; only bad debt the vault repaid
+    // from share holders' liquidity reduces total_assets
+    vault.total_assets = vault.total_assets.saturating_sub(socialized_loss);
+    update_vault_totals(
+        vault,
+        &prices,
+        collateral_before,
+        new_collateral,
+        debt_before,
+        new_debt,
+    )?;
+    
+    // Update position
+    user_position.collateral = new_collateral;
+    user_position.debt = new_debt;
+    user_position.leverage_bps = new_leverage;
+    user_position.last_interaction = clock.unix_timestamp;
+    
+    // Auction ends with the first fill; a new one is needed for further liquidation
+    user_position.liquidation_auction_start = 0;
     
     emit!(LiquidationEvent {
         vault: vault.key(),
@@ -119,95 +310,886 @@ pub fn liquidate(
         liquidated_user: user_position.user,
         collateral_seized: collateral_to_seize,
         debt_repaid: debt_to_repay,
+        protocol_fee,
         health_factor_before: health_factor,
         timestamp: clock.unix_timestamp,
     });
     
+    if auction_start > 0 {
+        emit!(LiquidationAuctionFilledEvent {
+            vault: vault.key(),
+            user: user_position.user,
+            liquidator: ctx.accounts.liquidator.key(),
+            bonus_bps: liquidation_bonus,
+            auction_elapsed: clock.unix_timestamp.saturating_sub(auction_start),
+            collateral_seized: collateral_to_seize,
+            debt_repaid: debt_to_repay,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    if bad_debt > 0 {
+        emit!(BadDebtEvent {
+            vault: vault.key(),
+            user: user_position.user,
+            bad_debt,
+            reserve_covered,
+            socialized_loss,
+            new_total_assets: vault.total_assets,
+            timestamp: clock.unix_timestamp,
+        });
+    
+        msg!("Wrote off {} bad debt ({} covered by reserve, {} socialized)", 
+             bad_debt, reserve_covered, socialized_loss);
+    }
+    
     msg!("Liquidated {} collateral, repaid {} debt", 
          collateral_to_seize, debt_to_repay);
     
     Ok(())
 }
 
+/// Start a Dutch liquidation auction for an unhealthy position (auction mode only)
+/// The liquidation bonus grows linearly from 0% at the start to the vault's
+/// maximum over the auction duration; the first liquidator fills at the current bonus
+pub fn start_liquidation_auction(
+    ctx: Context<StartLiquidationAuction>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    // Check vault is not paused
+    require!(!vault.paused, VaultError::VaultPaused);
+    
+    let max_bonus = vault.strategy_config
+        .auction_max_bonus_bps
+        .ok_or(VaultError::AuctionNotEnabled)?;
+    
+    // An expired auction can be restarted from a zero bonus
+    let auction_start = ctx.accounts.user_position.liquidation_auction_start;
+    require!(
+        auction_start == 0 || is_auction_expired(vault, auction_start, clock.unix_timestamp),
+        VaultError::AuctionAlreadyStarted
+    );
+    
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    let health_factor = prices.health_factor(
+        vault,
+        ctx.accounts.user_position.collateral,
+        ctx.accounts.user_position.debt,
+    )?;
+    
+    require!(
+        health_factor < get_liquidation_threshold(vault),
+        VaultError::LiquidationNotNeeded
+    );
+    
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.liquidation_auction_start = clock.unix_timestamp;
+    
+    emit!(LiquidationAuctionStartedEvent {
+        vault: vault.key(),
+        user: user_position.user,
+        starter: ctx.accounts.starter.key(),
+        health_factor,
+        max_bonus_bps: max_bonus,
+        duration: get_auction_duration(vault),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Started liquidation auction for {} at health {}", 
+         user_position.user, health_factor);
+    
+    Ok(())
+}
+
+/// Cancel a running liquidation auction once the position is healthy again
+/// Permissionless, so a stale auction start cannot carry a grown bonus into a
+/// later shortfall
+pub fn cancel_liquidation_auction(
+    ctx: Context<CancelLiquidationAuction>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.user_position.liquidation_auction_start > 0,
+        VaultError::AuctionNotStarted
+    );
+    
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    let health_factor = prices.health_factor(
+        vault,
+        ctx.accounts.user_position.collateral,
+        ctx.accounts.user_position.debt,
+    )?;
+    
+    require!(
+        health_factor >= get_liquidation_threshold(vault),
+        VaultError::LiquidationNeeded
+    );
+    
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.liquidation_auction_start = 0;
+    
+    emit!(LiquidationAuctionCancelledEvent {
+        vault: vault.key(),
+        user: user_position.user,
+        health_factor,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Cancelled liquidation auction for {} at health {}", 
+         user_position.user, health_factor);
+    
+    Ok(())
+}
+
 /// Adjust leverage for a leveraged vault position
-pub fn adjust_leverage(
-    ctx: Context<AdjustLeverage>,
+/// Collateral and debt live in a lending-market obligation owned by the vault
+/// (`StrategyConfig.lending_protocol`); the recorded debt is read back from the
+/// obligation so it always matches the lending side.
+/// A cross-asset debt leg (`StrategyConfig.debt_mint`) is looped through the swap
+/// adapter: borrowed debt tokens are swapped into collateral, and unwound collateral
+/// is swapped back into the debt token to repay
+pub fn adjust_leverage<'info>(
+    ctx: Context<'_, '_, '_, 'info, AdjustLeverage<'info>>,
     target_leverage_bps: u16,
     collateral_add: u64,
+    collateral_remove: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
-    let user_position = &mut ctx.accounts.user_position;
     let clock = Clock::get()?;
     
+    let lending_program = ctx.accounts.lending_program.to_account_info();
+    let debt_leg = get_debt_leg(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.liquidity_supply,
+        &ctx.accounts.vault_debt_token_account,
+        &ctx.accounts.debt_liquidity_supply,
+    )?;
+    let lending_accounts = |token_account: AccountInfo<'info>, liquidity_supply: AccountInfo<'info>| LendingAccounts {
+        market: ctx.accounts.lending_market.to_account_info(),
+        obligation: ctx.accounts.obligation.to_account_info(),
+        liquidity_supply,
+        token_account,
+        owner: ctx.accounts.vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let collateral_accounts = || lending_accounts(
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.liquidity_supply.to_account_info(),
+    );
+    let debt_accounts = || lending_accounts(
+        debt_leg.token_account.clone(),
+        debt_leg.liquidity_supply.clone(),
+    );
+    let leg_swap = || get_leg_swap(
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+        &ctx.accounts.swap_program,
+        &ctx.accounts.swap_pool,
+        &ctx.accounts.swap_pool_source,
+        &ctx.accounts.swap_pool_destination,
+    );
+    
+    let vault = &ctx.accounts.vault;
+    
     // Check vault is not paused
     require!(!vault.paused, VaultError::VaultPaused);
     
+    let cross_asset = is_cross_asset_debt(vault);
+    let max_slippage_bps = vault.strategy_config.max_swap_slippage_bps;
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    
     // Validate leverage
     require!(
         target_leverage_bps >= 10000 && target_leverage_bps <= vault.max_leverage_bps,
         VaultError::InvalidLeverage
     );
     
-    let leverage_before = user_position.leverage_bps;
-    let debt_before = user_position.debt;
+    let leverage_before = ctx.accounts.user_position.leverage_bps;
+    let debt_before = ctx.accounts.user_position.debt;
+    
+    let mut collateral = ctx.accounts.user_position.collateral
+        .checked_add(collateral_add)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_sub(collateral_remove)
+        .ok_or(VaultError::InsufficientFunds)?;
     
-    // If adding collateral, transfer from user
+    // Calculate new position size and debt (in debt-token units)
+    let new_position_size = calculate_leveraged_position(collateral, target_leverage_bps)?;
+    let new_debt = prices.collateral_in_debt(calculate_debt(new_position_size, collateral)?)?;
+    
+    // Collateral lost to swap slippage on a cross-asset loop, charged to the position
+    let mut swap_cost = 0;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    // If adding collateral, transfer from user and supply it to the obligation
     if collateral_add > 0 {
-        let cpi_accounts = Transfer {
+        let cpi_accounts = TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
             to: ctx.accounts.vault_token_account.to_account_info(),
             authority: ctx.accounts.user.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, collateral_add)?;
+        token_interface::transfer_checked(cpi_ctx, collateral_add, ctx.accounts.underlying_mint.decimals)?;
         
-        user_position.collateral = user_position.collateral
-            .checked_add(collateral_add)
+        lending::deposit_collateral(&lending_program, collateral_accounts(), collateral_add, signer)?;
+    }
+    
+    if new_debt < debt_before && cross_asset {
+        // Deleverage: unwind looped collateral and swap it into the debt token to repay
+        let repay_amount = debt_before
+            .checked_sub(new_debt)
             .ok_or(VaultError::MathOverflow)?;
-        
-        vault.total_assets = vault.total_assets
-            .checked_add(collateral_add)
+        let collateral_in = prices.collateral_for_debt(repay_amount, max_slippage_bps)?;
+    
+        lending::withdraw_collateral(&lending_program, collateral_accounts(), collateral_in, signer)?;
+        let debt_out = leg_swap()?.swap(
+            ctx.accounts.vault_token_account.to_account_info(),
+            debt_leg.token_account.clone(),
+            collateral_in,
+            repay_amount,
+            signer,
+        )?;
+        lending::repay(&lending_program, debt_accounts(), debt_out.min(debt_before), signer)?;
+    
+        swap_cost = collateral_in.saturating_sub(prices.debt_in_collateral(debt_out)?);
+    } else if new_debt < debt_before {
+        // Deleverage: repay from idle vault liquidity first, then unwind the same
+        // amount of looped collateral back into the vault so the vault is made whole
+        let repay_amount = debt_before
+            .checked_sub(new_debt)
+            .ok_or(VaultError::MathOverflow)?;
+    
+        lending::repay(&lending_program, debt_accounts(), repay_amount, signer)?;
+        lending::withdraw_collateral(&lending_program, collateral_accounts(), repay_amount, signer)?;
+    } else if new_debt > debt_before {
+        // Borrow into the vault, then loop the liquidity back in as collateral
+        let borrow_amount = new_debt
+            .checked_sub(debt_before)
             .ok_or(VaultError::MathOverflow)?;
+    
+        lending::borrow(&lending_program, debt_accounts(), borrow_amount, signer)?;
+    
+        let mut collateral_out = borrow_amount;
+        if cross_asset {
+            collateral_out = leg_swap()?.swap(
+                debt_leg.token_account.clone(),
+                ctx.accounts.vault_token_account.to_account_info(),
+                borrow_amount,
+                prices.min_collateral_out(borrow_amount, max_slippage_bps)?,
+                signer,
+            )?;
+            swap_cost = prices.debt_in_collateral(borrow_amount)?.saturating_sub(collateral_out);
+        }
+    
+        lending::deposit_collateral(&lending_program, collateral_accounts(), collateral_out, signer)?;
+    }
+    
+    collateral = collateral
+        .checked_sub(swap_cost)
+        .ok_or(VaultError::InsufficientFunds)?;
+    
+    // If removing collateral, withdraw it from the obligation and return it to the user
+    if collateral_remove > 0 {
+        lending::withdraw_collateral(&lending_program, collateral_accounts(), collateral_remove, signer)?;
+    
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, collateral_remove, ctx.accounts.underlying_mint.decimals)?;
     }
     
-    // Calculate new position size
-    let new_position_size = user_position.collateral
-        .checked_mul(target_leverage_bps as u64)
+    // Recorded debt mirrors the obligation on the lending side
+    let obligation = read_obligation(
+        &ctx.accounts.obligation.to_account_info(),
+        &lending_program.key(),
+    )?;
+    require!(
+        obligation.owner == ctx.accounts.vault.key(),
+        VaultError::InvalidLendingAccount
+    );
+    
+    // Position must stay healthy after the change
+    let health_factor = prices.health_factor(
+        &ctx.accounts.vault,
+        collateral,
+        obligation.borrowed_amount,
+    )?;
+    require!(
+        health_factor >= get_liquidation_threshold(&ctx.accounts.vault),
+        VaultError::LiquidationNeeded
+    );
+    
+    let collateral_before = ctx.accounts.user_position.collateral;
+    let vault = &mut ctx.accounts.vault;
+    let user_position = &mut ctx.accounts.user_position;
+    
+    // Collateral is tracked per position only; no shares back it, so it stays
+    // out of total_assets
+    update_vault_totals(
+        vault,
+        &prices,
+        collateral_before,
+        collateral,
+        debt_before,
+        obligation.borrowed_amount,
+    )?;
+    
+    // Vault-wide leverage is capped as well as each position's
+    require!(
+        vault.current_leverage_bps <= vault.max_leverage_bps,
+        VaultError::MaxLeverageExceeded
+    );
+    
+    user_position.collateral = collateral;
+    user_position.debt = obligation.borrowed_amount;
+    user_position.leverage_bps = target_leverage_bps;
+    user_position.last_interaction = clock.unix_timestamp;
+    
+    // Position is healthy after the change, so any running auction ends
+    user_position.liquidation_auction_start = 0;
+    
+    emit!(LeverageAdjustmentEvent {
+        vault: vault.key(),
+        user: user_position.user,
+        leverage_before: leverage_before as u64,
+        leverage_after: target_leverage_bps as u64,
+        collateral_added: collateral_add,
+        debt_added: user_position.debt.saturating_sub(debt_before),
+        collateral_removed: collateral_remove,
+        debt_repaid: debt_before.saturating_sub(user_position.debt),
+        health_factor_after: health_factor,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Adjusted leverage to {} bps, debt {}", target_leverage_bps, user_position.debt);
+    
+    Ok(())
+}
+
+/// Reduce an at-risk position's leverage before it becomes liquidatable
+/// Permissionless keeper instruction: once health falls below the vault's soft
+/// `deleverage_threshold_bps`, debt is repaid down to `deleverage_target_leverage_bps`
+/// with no liquidation penalty. The keeper is paid a small fee from the position's collateral
+pub fn auto_deleverage<'info>(
+    ctx: Context<'_, '_, '_, 'info, AutoDeleverage<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    let lending_program = ctx.accounts.lending_program.to_account_info();
+    let debt_leg = get_debt_leg(
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.liquidity_supply,
+        &ctx.accounts.vault_debt_token_account,
+        &ctx.accounts.debt_liquidity_supply,
+    )?;
+    let lending_accounts = |token_account: AccountInfo<'info>, liquidity_supply: AccountInfo<'info>| LendingAccounts {
+        market: ctx.accounts.lending_market.to_account_info(),
+        obligation: ctx.accounts.obligation.to_account_info(),
+        liquidity_supply,
+        token_account,
+        owner: ctx.accounts.vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+    let collateral_accounts = || lending_accounts(
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.liquidity_supply.to_account_info(),
+    );
+    let debt_accounts = || lending_accounts(
+        debt_leg.token_account.clone(),
+        debt_leg.liquidity_supply.clone(),
+    );
+    
+    let vault = &ctx.accounts.vault;
+    let user_position = &ctx.accounts.user_position;
+    
+    // Check vault is not paused
+    require!(!vault.paused, VaultError::VaultPaused);
+    
+    let deleverage_threshold = vault.strategy_config
+        .deleverage_threshold_bps
+        .ok_or(VaultError::AutoDeleverageNotEnabled)? as u64;
+    let target_leverage = vault.strategy_config
+        .deleverage_target_leverage_bps
+        .unwrap_or(15000); // 1.5x default
+    let keeper_fee_bps = vault.strategy_config
+        .keeper_fee_bps
+        .unwrap_or(10); // 0.1% of repaid debt default
+    
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    let health_before = prices.health_factor(vault, user_position.collateral, user_position.debt)?;
+    
+    require!(
+        health_before < deleverage_threshold,
+        VaultError::DeleverageNotNeeded
+    );
+    
+    // Debt at the safe target leverage
+    let target_position_size = calculate_leveraged_position(user_position.collateral, target_leverage)?;
+    let target_debt = prices.collateral_in_debt(
+        calculate_debt(target_position_size, user_position.collateral)?,
+    )?;
+    
+    require!(target_debt < user_position.debt, VaultError::DeleverageNotNeeded);
+    
+    let repay_amount = user_position.debt
+        .checked_sub(target_debt)
+        .ok_or(VaultError::MathOverflow)?;
+    let keeper_fee = prices.debt_in_collateral(repay_amount)?
+        .checked_mul(keeper_fee_bps as u64)
         .ok_or(VaultError::MathOverflow)?
         .checked_div(10000)
         .ok_or(VaultError::MathOverflow)?;
     
-    // Calculate new debt
-    let new_debt = new_position_size
-        .checked_sub(user_position.collateral)
-        .ok_or(VaultError::MathOverflow)?;
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    // Collateral lost to swap slippage on a cross-asset unwind, charged to the position
+    let mut swap_cost = 0;
+    
+    if is_cross_asset_debt(vault) {
+        // Unwind looped collateral (plus the keeper fee) and swap it into the debt token to repay
+        let collateral_in = prices.collateral_for_debt(
+            repay_amount,
+            vault.strategy_config.max_swap_slippage_bps,
+        )?;
+        lending::withdraw_collateral(
+            &lending_program,
+            collateral_accounts(),
+            collateral_in
+                .checked_add(keeper_fee)
+                .ok_or(VaultError::MathOverflow)?,
+            signer,
+        )?;
+    
+        let leg_swap = get_leg_swap(
+            vault,
+            &ctx.accounts.token_program,
+            &ctx.accounts.swap_program,
+            &ctx.accounts.swap_pool,
+            &ctx.accounts.swap_pool_source,
+            &ctx.accounts.swap_pool_destination,
+        )?;
+        let debt_out = leg_swap.swap(
+            ctx.accounts.vault_token_account.to_account_info(),
+            debt_leg.token_account.clone(),
+            collateral_in,
+            repay_amount,
+            signer,
+        )?;
+        lending::repay(&lending_program, debt_accounts(), debt_out.min(user_position.debt), signer)?;
+    
+        swap_cost = collateral_in.saturating_sub(prices.debt_in_collateral(debt_out)?);
+    } else {
+        // Repay from idle vault liquidity, then unwind the same amount of looped
+        // collateral (plus the keeper fee) back into the vault
+        lending::repay(&lending_program, debt_accounts(), repay_amount, signer)?;
+        lending::withdraw_collateral(
+            &lending_program,
+            collateral_accounts(),
+            repay_amount
+                .checked_add(keeper_fee)
+                .ok_or(VaultError::MathOverflow)?,
+            signer,
+        )?;
+    }
+    
+    if keeper_fee > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.underlying_mint.to_account_info(),
+            to: ctx.accounts.keeper_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, keeper_fee, ctx.accounts.underlying_mint.decimals)?;
+    }
+    
+    // Recorded debt mirrors the obligation on the lending side
+    let obligation = read_obligation(
+        &ctx.accounts.obligation.to_account_info(),
+        &lending_program.key(),
+    )?;
+    
+    let collateral_before = user_position.collateral;
+    let debt_before = user_position.debt;
+    let leverage_before = user_position.leverage_bps;
+    let new_collateral = collateral_before
+        .checked_sub(keeper_fee)
+        .and_then(|collateral| collateral.checked_sub(swap_cost))
+        .ok_or(VaultError::InsufficientFunds)?;
+    let new_debt = obligation.borrowed_amount;
+    let health_after = prices.health_factor(vault, new_collateral, new_debt)?;
+    let new_leverage = calculate_leverage_bps(new_collateral, prices.debt_in_collateral(new_debt)?)?;
+    
+    let vault = &mut ctx.accounts.vault;
+    let user_position = &mut ctx.accounts.user_position;
+    
+    // Keeper fee comes out of the position's collateral, not total_assets
+    update_vault_totals(
+        vault,
+        &prices,
+        collateral_before,
+        new_collateral,
+        debt_before,
+        new_debt,
+    )?;
     
-    // In production, would interact with lending protocol to adjust debt
-    // For now, just update state
+    user_position.collateral = new_collateral;
     user_position.debt = new_debt;
-    user_position.leverage_bps = target_leverage_bps;
+    user_position.leverage_bps = new_leverage;
     user_position.last_interaction = clock.unix_timestamp;
     
-    vault.current_leverage_bps = target_leverage_bps;
+    // Deleveraging back above the liquidation threshold ends any running auction
+    if health_after >= get_liquidation_threshold(vault) {
+        user_position.liquidation_auction_start = 0;
+    }
     
-    emit!(LeverageAdjustmentEvent {
+    emit!(AutoDeleverageEvent {
         vault: vault.key(),
         user: user_position.user,
+        keeper: ctx.accounts.keeper.key(),
         leverage_before,
-        leverage_after: target_leverage_bps,
-        collateral_added: collateral_add,
-        debt_added: new_debt
-            .checked_sub(debt_before)
-            .unwrap_or(0),
+        leverage_after: new_leverage,
+        debt_repaid: debt_before.saturating_sub(new_debt),
+        keeper_fee,
+        health_factor_before: health_before,
+        health_factor_after: health_after,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Auto-deleveraged {} to {} bps, keeper fee {}", 
+         user_position.user, new_leverage, keeper_fee);
+    
+    Ok(())
+}
+
+/// Emit a vault-wide health summary of all leveraged positions
+pub fn report_vault_health(ctx: Context<ReportVaultHealth>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+    
+    let prices = get_leg_prices(vault, &ctx.accounts.underlying_mint, &ctx.accounts.debt_mint)?;
+    let health_factor = prices.health_factor(vault, vault.total_collateral, vault.total_debt)?;
+    
+    emit!(VaultHealthEvent {
+        vault: vault.key(),
+        total_collateral: vault.total_collateral,
+        total_debt: vault.total_debt,
+        leverage_bps: vault.current_leverage_bps,
+        max_leverage_bps: vault.max_leverage_bps,
+        health_factor,
+        liquidation_threshold_bps: get_liquidation_threshold(vault),
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Adjusted leverage to {} bps", target_leverage_bps);
+    msg!("Vault health: leverage {} bps, health factor {}", 
+         vault.current_leverage_bps, health_factor);
     
     Ok(())
 }
 
+/// Oracle prices and decimals of a leveraged position's collateral and debt legs
+struct LegPrices {
+    collateral_price: i64,
+    collateral_decimals: u8,
+    debt_price: i64,
+    debt_decimals: u8,
+}
+
+impl LegPrices {
+    /// Oracle-priced health factor of a collateral/debt pair
+    fn health_factor(&self, vault: &Vault, collateral: u64, debt: u64) -> Result<u64> {
+        let collateral_factor = vault.strategy_config
+            .collateral_factor_bps
+            .unwrap_or(8000); // 80% default
+    
+        calculate_position_health_factor(
+            collateral,
+            self.collateral_price,
+            self.collateral_decimals,
+            debt,
+            self.debt_price,
+            self.debt_decimals,
+            collateral_factor,
+        )
+    }
+    
+    /// Value a debt-token amount in collateral units
+    fn debt_in_collateral(&self, amount: u64) -> Result<u64> {
+        convert_at_oracle_price(
+            amount,
+            self.debt_price,
+            self.debt_decimals,
+            self.collateral_price,
+            self.collateral_decimals,
+        )
+    }
+    
+    /// Value a collateral amount in debt-token units
+    fn collateral_in_debt(&self, amount: u64) -> Result<u64> {
+        convert_at_oracle_price(
+            amount,
+            self.collateral_price,
+            self.collateral_decimals,
+            self.debt_price,
+            self.debt_decimals,
+        )
+    }
+    
+    /// Oracle-bounded minimum collateral a swap of `debt_in` debt tokens must return
+    fn min_collateral_out(&self, debt_in: u64, max_slippage_bps: u16) -> Result<u64> {
+        calculate_min_swap_output(
+            debt_in,
+            self.debt_price,
+            self.debt_decimals,
+            self.collateral_price,
+            self.collateral_decimals,
+            max_slippage_bps,
+        )
+    }
+    
+    /// Collateral to swap so that at least `debt_out` debt tokens come back
+    fn collateral_for_debt(&self, debt_out: u64, max_slippage_bps: u16) -> Result<u64> {
+        calculate_max_swap_input(
+            debt_out,
+            self.collateral_price,
+            self.collateral_decimals,
+            self.debt_price,
+            self.debt_decimals,
+            max_slippage_bps,
+        )
+    }
+}
+
+/// Read validated oracle prices for a vault's collateral and debt legs
+fn get_leg_prices(
+    vault: &Vault,
+    underlying_mint: &InterfaceAccount<Mint>,
+    debt_mint: &Option<InterfaceAccount<Mint>>,
+) -> Result<LegPrices> {
+    let (collateral_price, debt_price) = get_position_prices(vault)?;
+    
+    Ok(LegPrices {
+        collateral_price,
+        collateral_decimals: underlying_mint.decimals,
+        debt_price,
+        debt_decimals: get_debt_decimals(vault, underlying_mint, debt_mint)?,
+    })
+}
+
+/// Whether positions borrow a mint other than the underlying
+fn is_cross_asset_debt(vault: &Vault) -> bool {
+    vault.strategy_config
+        .debt_mint
+        .is_some_and(|mint| mint != vault.underlying_mint)
+}
+
+/// Decimals of the position's debt mint (the underlying unless a debt mint is configured)
+fn get_debt_decimals(
+    vault: &Vault,
+    underlying_mint: &InterfaceAccount<Mint>,
+    debt_mint: &Option<InterfaceAccount<Mint>>,
+) -> Result<u8> {
+    if is_cross_asset_debt(vault) {
+        let debt_mint = debt_mint.as_ref().ok_or(VaultError::InvalidMint)?;
+        Ok(debt_mint.decimals)
+    } else {
+        Ok(underlying_mint.decimals)
+    }
+}
+
+/// Mint the position's debt is repaid in
+fn get_debt_mint_info<'info>(
+    underlying_mint: &InterfaceAccount<'info, Mint>,
+    debt_mint: &Option<InterfaceAccount<'info, Mint>>,
+) -> AccountInfo<'info> {
+    debt_mint
+        .as_ref()
+        .map(|mint| mint.to_account_info())
+        .unwrap_or_else(|| underlying_mint.to_account_info())
+}
+
+/// Vault token account and lending reserve supply the position's debt moves through
+struct DebtLeg<'info> {
+    token_account: AccountInfo<'info>,
+    liquidity_supply: AccountInfo<'info>,
+}
+
+/// Resolve the debt leg: the underlying's own accounts unless debt is cross-asset
+fn get_debt_leg<'info>(
+    vault: &Vault,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    liquidity_supply: &UncheckedAccount<'info>,
+    vault_debt_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    debt_liquidity_supply: &Option<UncheckedAccount<'info>>,
+) -> Result<DebtLeg<'info>> {
+    if !is_cross_asset_debt(vault) {
+        return Ok(DebtLeg {
+            token_account: vault_token_account.to_account_info(),
+            liquidity_supply: liquidity_supply.to_account_info(),
+        });
+    }
+    
+    let token_account = vault_debt_token_account
+        .as_ref()
+        .ok_or(VaultError::InvalidMint)?;
+    let liquidity_supply = debt_liquidity_supply
+        .as_ref()
+        .ok_or(VaultError::InvalidLendingAccount)?;
+    
+    Ok(DebtLeg {
+        token_account: token_account.to_account_info(),
+        liquidity_supply: liquidity_supply.to_account_info(),
+    })
+}
+
+/// Swap route between the underlying and a cross-asset debt mint
+struct LegSwap<'info> {
+    program: AccountInfo<'info>,
+    pool: AccountInfo<'info>,
+    pool_source: AccountInfo<'info>,
+    pool_destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+}
+
+impl<'info> LegSwap<'info> {
+    /// Swap `amount_in` between two vault-owned token accounts, returning the amount received
+    fn swap(
+        &self,
+        source: AccountInfo<'info>,
+        destination: AccountInfo<'info>,
+        amount_in: u64,
+        min_amount_out: u64,
+        signer: &[&[&[u8]]],
+    ) -> Result<u64> {
+        let balance_before = accessor::amount(&destination)?;
+    
+        swap(
+            &self.program,
+            SwapAccounts {
+                pool: self.pool.clone(),
+                pool_source: self.pool_source.clone(),
+                pool_destination: self.pool_destination.clone(),
+                source,
+                destination: destination.clone(),
+                authority: self.authority.clone(),
+                token_program: self.token_program.clone(),
+            },
+            amount_in,
+            min_amount_out,
+            signer,
+        )?;
+    
+        // Credit only what actually arrived in the vault
+        let amount_out = accessor::amount(&destination)?
+            .checked_sub(balance_before)
+            .ok_or(VaultError::MathOverflow)?;
+        require!(amount_out >= min_amount_out, VaultError::SlippageExceeded);
+    
+        Ok(amount_out)
+    }
+}
+
+/// Resolve the swap route of a cross-asset debt leg, validated against strategy_config.swap_program
+fn get_leg_swap<'info>(
+    vault: &Account<'info, Vault>,
+    token_program: &Interface<'info, TokenInterface>,
+    swap_program: &Option<UncheckedAccount<'info>>,
+    swap_pool: &Option<UncheckedAccount<'info>>,
+    swap_pool_source: &Option<UncheckedAccount<'info>>,
+    swap_pool_destination: &Option<UncheckedAccount<'info>>,
+) -> Result<LegSwap<'info>> {
+    let swap_program = swap_program
+        .as_ref()
+        .ok_or(VaultError::InvalidSwapProgram)?;
+    require!(
+        vault.strategy_config.swap_program == Some(swap_program.key()),
+        VaultError::InvalidSwapProgram
+    );
+    let swap_pool = swap_pool
+        .as_ref()
+        .ok_or(VaultError::InvalidSwapProgram)?;
+    let pool_source = swap_pool_source
+        .as_ref()
+        .ok_or(VaultError::InvalidSwapProgram)?;
+    let pool_destination = swap_pool_destination
+        .as_ref()
+        .ok_or(VaultError::InvalidSwapProgram)?;
+    
+    Ok(LegSwap {
+        program: swap_program.to_account_info(),
+        pool: swap_pool.to_account_info(),
+        pool_source: pool_source.to_account_info(),
+        pool_destination: pool_destination.to_account_info(),
+        authority: vault.to_account_info(),
+        token_program: token_program.to_account_info(),
+    })
+}
+
+/// Apply a position's collateral and debt change to the vault-wide totals
+/// and re-derive the vault's leverage from them
+fn update_vault_totals(
+    vault: &mut Vault,
+    prices: &LegPrices,
+    collateral_before: u64,
+    collateral_after: u64,
+    debt_before: u64,
+    debt_after: u64,
+) -> Result<()> {
+    vault.total_collateral = vault.total_collateral
+        .saturating_sub(collateral_before)
+        .checked_add(collateral_after)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_debt = vault.total_debt
+        .saturating_sub(debt_before)
+        .checked_add(debt_after)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    vault.current_leverage_bps = calculate_leverage_bps(
+        vault.total_collateral,
+        prices.debt_in_collateral(vault.total_debt)?,
+    )?;
+    
+    Ok(())
+}
+
+/// Time over which an auction's bonus ramps up to its maximum
+fn get_auction_duration(vault: &Vault) -> i64 {
+    vault.strategy_config
+        .auction_duration
+        .unwrap_or(600) // 10 minutes default
+}
+
+/// Whether an auction has run past its duration without being filled
+fn is_auction_expired(vault: &Vault, auction_start: i64, current_timestamp: i64) -> bool {
+    current_timestamp.saturating_sub(auction_start) > get_auction_duration(vault)
+}
+
+/// Health factor below which a position can be liquidated
+fn get_liquidation_threshold(vault: &Vault) -> u64 {
+    vault.strategy_config
+        .liquidation_threshold_bps
+        .unwrap_or(11000) as u64 // 1.1x = 11000 bps
+}
+
 #[derive(Accounts)]
 pub struct Liquidate<'info> {
     #[account(
@@ -218,6 +1200,7 @@ pub struct Liquidate<'info> {
     pub vault: Account<'info, Vault>,
     
     #[account(
+        mut,
         constraint = user_position.vault == vault.key()
     )]
     pub user_position: Account<'info, UserPosition>,
@@ -227,18 +1210,165 @@ pub struct Liquidate<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = liquidator_token_account.mint == vault.underlying_mint,
         constraint = liquidator_token_account.owner == liquidator.key()
     )]
-    pub liquidator_token_account: Account<'info, TokenAccount>,
+    pub liquidator_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Liquidator account the debt is repaid from (required when debt is not the underlying)
+    #[account(
+        mut,
+        constraint = Some(liquidator_debt_token_account.mint) == vault.strategy_config.debt_mint @ VaultError::InvalidMint,
+        constraint = liquidator_debt_token_account.owner == liquidator.key()
+    )]
+    pub liquidator_debt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// Insurance reserve, drawn first to write off bad debt
+    #[account(
+        mut,
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury account receiving the protocol cut of the liquidation bonus
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == vault.underlying_mint,
+        constraint = treasury_token_account.owner == global_state.treasury
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
     
     pub liquidator: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Lending adapter program, validated against strategy_config.lending_protocol
+    #[account(
+        constraint = Some(lending_program.key()) == vault.strategy_config.lending_protocol @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Lending market, validated against strategy_config.lending_market
+    #[account(
+        mut,
+        constraint = Some(lending_market.key()) == vault.strategy_config.lending_market @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Position's obligation on the lending market, validated against user_position.obligation
+    #[account(
+        mut,
+        constraint = obligation.key() == user_position.obligation @ VaultError::InvalidLendingAccount
+    )]
+    pub obligation: UncheckedAccount<'info>,
+    
+    /// CHECK: Market liquidity supply, validated by the lending program
+    #[account(mut)]
+    pub liquidity_supply: UncheckedAccount<'info>,
+    
+    /// Vault account for the debt token (required when debt is not the underlying)
+    #[account(
+        mut,
+        constraint = Some(vault_debt_token_account.mint) == vault.strategy_config.debt_mint @ VaultError::InvalidMint,
+        constraint = vault_debt_token_account.owner == vault.key()
+    )]
+    pub vault_debt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Debt reserve liquidity supply, validated by the lending program
+    /// (required when debt is not the underlying)
+    #[account(mut)]
+    pub debt_liquidity_supply: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap adapter program, validated against strategy_config.swap_program
+    /// (swap accounts are only required when debt is not the underlying)
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap pool between the underlying and the debt mint, validated by the swap program
+    #[account(mut)]
+    pub swap_pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault receiving the swapped-out leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_source: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault paying out the other leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_destination: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct StartLiquidationAuction<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = user_position.vault == vault.key()
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub starter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLiquidationAuction<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = user_position.vault == vault.key()
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
 }
 
 #[derive(Accounts)]
@@ -262,17 +1392,199 @@ pub struct AdjustLeverage<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = user_token_account.mint == vault.underlying_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Lending adapter program, validated against strategy_config.lending_protocol
+    #[account(
+        constraint = Some(lending_program.key()) == vault.strategy_config.lending_protocol @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Lending market, validated against strategy_config.lending_market
+    #[account(
+        mut,
+        constraint = Some(lending_market.key()) == vault.strategy_config.lending_market @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Position's obligation on the lending market, validated against user_position.obligation
+    #[account(
+        mut,
+        constraint = obligation.key() == user_position.obligation @ VaultError::InvalidLendingAccount
+    )]
+    pub obligation: UncheckedAccount<'info>,
+    
+    /// CHECK: Market liquidity supply, validated by the lending program
+    #[account(mut)]
+    pub liquidity_supply: UncheckedAccount<'info>,
+    
+    /// Vault account for the debt token (required when debt is not the underlying)
+    #[account(
+        mut,
+        constraint = Some(vault_debt_token_account.mint) == vault.strategy_config.debt_mint @ VaultError::InvalidMint,
+        constraint = vault_debt_token_account.owner == vault.key()
+    )]
+    pub vault_debt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Debt reserve liquidity supply, validated by the lending program
+    /// (required when debt is not the underlying)
+    #[account(mut)]
+    pub debt_liquidity_supply: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap adapter program, validated against strategy_config.swap_program
+    /// (swap accounts are only required when debt is not the underlying)
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap pool between the underlying and the debt mint, validated by the swap program
+    #[account(mut)]
+    pub swap_pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault receiving the swapped-out leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_source: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault paying out the other leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_destination: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct AutoDeleverage<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = user_position.vault == vault.key()
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.mint == vault.underlying_mint,
+        constraint = vault_token_account.owner == vault.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == vault.underlying_mint,
+        constraint = keeper_token_account.owner == keeper.key()
+    )]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub keeper: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// CHECK: Lending adapter program, validated against strategy_config.lending_protocol
+    #[account(
+        constraint = Some(lending_program.key()) == vault.strategy_config.lending_protocol @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Lending market, validated against strategy_config.lending_market
+    #[account(
+        mut,
+        constraint = Some(lending_market.key()) == vault.strategy_config.lending_market @ VaultError::InvalidLendingAccount
+    )]
+    pub lending_market: UncheckedAccount<'info>,
+    
+    /// CHECK: Position's obligation on the lending market, validated against user_position.obligation
+    #[account(
+        mut,
+        constraint = obligation.key() == user_position.obligation @ VaultError::InvalidLendingAccount
+    )]
+    pub obligation: UncheckedAccount<'info>,
+    
+    /// CHECK: Market liquidity supply, validated by the lending program
+    #[account(mut)]
+    pub liquidity_supply: UncheckedAccount<'info>,
+    
+    /// Vault account for the debt token (required when debt is not the underlying)
+    #[account(
+        mut,
+        constraint = Some(vault_debt_token_account.mint) == vault.strategy_config.debt_mint @ VaultError::InvalidMint,
+        constraint = vault_debt_token_account.owner == vault.key()
+    )]
+    pub vault_debt_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// CHECK: Debt reserve liquidity supply, validated by the lending program
+    /// (required when debt is not the underlying)
+    #[account(mut)]
+    pub debt_liquidity_supply: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap adapter program, validated against strategy_config.swap_program
+    /// (swap accounts are only required when debt is not the underlying)
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap pool between the underlying and the debt mint, validated by the swap program
+    #[account(mut)]
+    pub swap_pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault receiving the swapped-out leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_source: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault paying out the other leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_destination: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ReportVaultHealth<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Debt mint (required when strategy_config.debt_mint differs from the underlying)
+    #[account(
+        constraint = Some(debt_mint.key()) == vault.strategy_config.debt_mint @ VaultError::InvalidMint
+    )]
+    pub debt_mint: Option<InterfaceAccount<'info, Mint>>,
 }
@@ -0,0 +1,519 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::utils::{
+    calculate_boost_debt, calculate_lock_weight, calculate_locked_profit, calculate_pending_boost,
+    calculate_shares_to_mint, BOOST_PRECISION,
+};
+use crate::events::{SharesLockedEvent, SharesUnlockedEvent};
+use super::rewards::checkpoint_rewards;
+
+/// Lock tiers: (lock duration in seconds, reward weight multiplier in basis points)
+pub const LOCK_TIERS: [(i64, u16); 3] = [
+    (30 * 86400, 11000),  // 30 days, 1.1x
+    (90 * 86400, 15000),  // 90 days, 1.5x
+    (180 * 86400, 20000), // 180 days, 2x
+];
+
+/// Enable share locking on a vault: create the lock escrow and set the share
+/// of harvested rewards boosted to lockers and the early-unlock penalty
+pub fn initialize_locks(
+    ctx: Context<InitializeLocks>,
+    lock_boost_bps: u16,
+    early_unlock_penalty_bps: u16,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can enable locks
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    set_lock_params(vault, lock_boost_bps, early_unlock_penalty_bps)?;
+    
+    msg!("Locks enabled for vault {}: {} bps boost, {} bps early-unlock penalty",
+         vault.vault_id, lock_boost_bps, early_unlock_penalty_bps);
+    
+    Ok(())
+}
+
+/// Update the lock boost and early-unlock penalty (authority only)
+pub fn update_lock_params(
+    ctx: Context<UpdateLockParams>,
+    lock_boost_bps: u16,
+    early_unlock_penalty_bps: u16,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    set_lock_params(vault, lock_boost_bps, early_unlock_penalty_bps)
+}
+
+fn set_lock_params(
+    vault: &mut Vault,
+    lock_boost_bps: u16,
+    early_unlock_penalty_bps: u16,
+) -> Result<()> {
+    // Boost at most half of harvested rewards, penalize at most half of the lock
+    require!(
+        lock_boost_bps <= 5000 && early_unlock_penalty_bps <= 5000,
+        VaultError::InvalidLockConfig
+    );
+    
+    vault.lock_boost_bps = lock_boost_bps;
+    vault.early_unlock_penalty_bps = early_unlock_penalty_bps;
+    
+    Ok(())
+}
+
+/// Create a user's lock account for a vault
+pub fn initialize_user_lock(ctx: Context<InitializeUserLock>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lock;
+    user_lock.user = ctx.accounts.user.key();
+    user_lock.vault = ctx.accounts.vault.key();
+    user_lock.shares = 0;
+    user_lock.weight = 0;
+    user_lock.tier = 0;
+    user_lock.unlock_ts = 0;
+    user_lock.boost_debt = 0;
+    user_lock.pending_boost_shares = 0;
+    user_lock.bump = ctx.bumps.user_lock;
+    
+    Ok(())
+}
+
+/// Lock vault shares into the escrow for a tier (call right after deposit to
+/// deposit locked). Adding to an existing lock keeps or raises its tier and
+/// never shortens its expiry
+pub fn lock_shares(
+    ctx: Context<LockShares>,
+    shares: u64,
+    tier: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    let user_lock = &mut ctx.accounts.user_lock;
+    
+    require!(!vault.paused, VaultError::VaultPaused);
+    require!(shares > 0, VaultError::InsufficientFunds);
+    let (duration, multiplier_bps) = *LOCK_TIERS
+        .get(tier as usize)
+        .ok_or(VaultError::InvalidLockTier)?;
+    require!(
+        user_lock.shares == 0 || tier >= user_lock.tier,
+        VaultError::InvalidLockTier
+    );
+    
+    // Settle boost earned at the old weight
+    user_lock.pending_boost_shares = calculate_pending_boost(
+        user_lock.weight,
+        vault.acc_boost_per_weight,
+        user_lock.boost_debt,
+    )?
+    .checked_add(user_lock.pending_boost_shares)
+    .ok_or(VaultError::MathOverflow)?;
+    
+    // Move the shares into the vault-owned escrow. Shares are burned and
+    // re-minted rather than transferred, which would re-enter this program
+    // through the share mint's transfer hook
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.user_share_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let share_program = ctx.accounts.share_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(share_program.clone(), cpi_accounts);
+    token_interface::burn(cpi_ctx, shares)?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.lock_escrow.to_account_info(),
+        authority: vault_info,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(share_program, cpi_accounts, signer);
+    token_interface::mint_to(cpi_ctx, shares)?;
+    
+    let unlock_ts = clock.unix_timestamp
+        .checked_add(duration)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    user_lock.shares = user_lock.shares
+        .checked_add(shares)
+        .ok_or(VaultError::MathOverflow)?;
+    user_lock.tier = tier;
+    user_lock.unlock_ts = user_lock.unlock_ts.max(unlock_ts);
+    
+    let new_weight = calculate_lock_weight(user_lock.shares, multiplier_bps)?;
+    vault.total_lock_weight = vault.total_lock_weight
+        .checked_sub(user_lock.weight)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(new_weight)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_locked_shares = vault.total_locked_shares
+        .checked_add(shares)
+        .ok_or(VaultError::MathOverflow)?;
+    user_lock.weight = new_weight;
+    user_lock.boost_debt = calculate_boost_debt(new_weight, vault.acc_boost_per_weight)?;
+    
+    emit!(SharesLockedEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        shares,
+        total_locked: user_lock.shares,
+        tier,
+        weight: user_lock.weight,
+        unlock_ts: user_lock.unlock_ts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Locked {} shares in tier {} until {}", shares, tier, user_lock.unlock_ts);
+    
+    Ok(())
+}
+
+/// Unlock all of a user's locked shares plus their accrued boost shares
+/// Before expiry, `early_unlock_penalty_bps` of the locked shares is forfeited
+/// and shared out to the remaining lockers by weight
+pub fn unlock_shares(ctx: Context<UnlockShares>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    let user_lock = &mut ctx.accounts.user_lock;
+    
+    require!(user_lock.shares > 0, VaultError::NothingLocked);
+    
+    let boost_shares = calculate_pending_boost(
+        user_lock.weight,
+        vault.acc_boost_per_weight,
+        user_lock.boost_debt,
+    )?
+    .checked_add(user_lock.pending_boost_shares)
+    .ok_or(VaultError::MathOverflow)?;
+    
+    let early = clock.unix_timestamp < user_lock.unlock_ts;
+    let penalty_shares = if early {
+        user_lock.shares
+            .checked_mul(vault.early_unlock_penalty_bps as u64)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(VaultError::MathOverflow)?
+    } else {
+        0
+    };
+    let returned_shares = user_lock.shares
+        .checked_sub(penalty_shares)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Remove this lock's weight before distributing its penalty
+    vault.total_lock_weight = vault.total_lock_weight
+        .checked_sub(user_lock.weight)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_locked_shares = vault.total_locked_shares
+        .checked_sub(user_lock.shares)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    let share_program = ctx.accounts.share_token_program.to_account_info();
+    
+    // Release the whole lock from the escrow (burned and re-minted, like locking)
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.lock_escrow.to_account_info(),
+        authority: vault_info.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(share_program.clone(), cpi_accounts, signer);
+    token_interface::burn(cpi_ctx, user_lock.shares)?;
+    
+    // Forfeited shares are re-issued to the other lockers as boost; with no
+    // other lockers they accrue to every share holder
+    if penalty_shares > 0 {
+        if vault.total_lock_weight > 0 {
+            let acc_increase = (penalty_shares as u128)
+                .checked_mul(BOOST_PRECISION)
+                .ok_or(VaultError::MathOverflow)?
+                .checked_div(vault.total_lock_weight as u128)
+                .ok_or(VaultError::MathOverflow)?;
+            vault.acc_boost_per_weight = vault.acc_boost_per_weight
+                .checked_add(acc_increase)
+                .ok_or(VaultError::MathOverflow)?;
+        } else {
+            vault.total_shares = vault.total_shares
+                .checked_sub(penalty_shares)
+                .ok_or(VaultError::MathOverflow)?;
+        }
+    }
+    
+    // Return the remaining locked shares plus the boost shares, which are
+    // already counted in total_shares
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.user_share_account.to_account_info(),
+        authority: vault_info,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(share_program, cpi_accounts, signer);
+    token_interface::mint_to(
+        cpi_ctx,
+        returned_shares
+            .checked_add(boost_shares)
+            .ok_or(VaultError::MathOverflow)?,
+    )?;
+    
+    // Locked shares kept earning emission rewards; the penalty stops, the boost starts
+    checkpoint_rewards(
+        &ctx.accounts.reward_distributor,
+        &ctx.accounts.user_rewards,
+        boost_shares,
+        penalty_shares,
+    )?;
+    
+    emit!(SharesUnlockedEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        shares_returned: returned_shares,
+        penalty_shares,
+        boost_shares,
+        early,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    user_lock.shares = 0;
+    user_lock.weight = 0;
+    user_lock.tier = 0;
+    user_lock.unlock_ts = 0;
+    user_lock.boost_debt = 0;
+    user_lock.pending_boost_shares = 0;
+    
+    msg!("Unlocked {} shares (penalty {}, boost {})", returned_shares, penalty_shares, boost_shares);
+    
+    Ok(())
+}
+
+/// Issue the lockers' boosted portion of harvested rewards as new shares at
+/// the current share price, credited to locks by weight. Returns the rewards
+/// used (0 if nothing is locked), which must not be compounded again
+pub fn allocate_lock_boost(
+    vault: &mut Vault,
+    rewards: u64,
+    timestamp: i64,
+) -> Result<u64> {
+    if vault.total_lock_weight == 0 || vault.lock_boost_bps == 0 {
+        return Ok(0);
+    }
+    
+    let boost = rewards
+        .checked_mul(vault.lock_boost_bps as u64)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let locked_profit = calculate_locked_profit(vault, timestamp)?;
+    let boost_shares = calculate_shares_to_mint(
+        boost,
+        vault.total_assets,
+        locked_profit,
+        vault.total_shares,
+    )?;
+    if boost_shares == 0 {
+        return Ok(0);
+    }
+    
+    vault.total_assets = vault.total_assets
+        .checked_add(boost)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_shares = vault.total_shares
+        .checked_add(boost_shares)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let acc_increase = (boost_shares as u128)
+        .checked_mul(BOOST_PRECISION)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(vault.total_lock_weight as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.acc_boost_per_weight = vault.acc_boost_per_weight
+        .checked_add(acc_increase)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok(boost)
+}
+
+#[derive(Accounts)]
+pub struct InitializeLocks<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    /// Vault-owned share account holding locked shares
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"lock_escrow", vault.key().as_ref()],
+        bump,
+        token::mint = share_mint,
+        token::authority = vault,
+        token::token_program = share_token_program
+    )]
+    pub lock_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = share_mint.key() == vault.share_mint
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLockParams<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserLock<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = UserLock::LEN,
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: Account<'info, UserLock>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LockShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump = user_lock.bump
+    )]
+    pub user_lock: Account<'info, UserLock>,
+    
+    #[account(
+        mut,
+        seeds = [b"lock_escrow", vault.key().as_ref()],
+        bump
+    )]
+    pub lock_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    pub share_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UnlockShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump = user_lock.bump
+    )]
+    pub user_lock: Account<'info, UserLock>,
+    
+    #[account(
+        mut,
+        seeds = [b"lock_escrow", vault.key().as_ref()],
+        bump
+    )]
+    pub lock_escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,
+}
@@ -0,0 +1,218 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::VaultError;
+
+/// Vault layout written by the original program, before the fields added since
+/// (harvest bounty, profit unlock, loss reporting, reserve, leverage totals,
+/// hedging, dual-asset, liquid staking and lock state)
+#[derive(AnchorDeserialize)]
+struct LegacyVault {
+    vault_id: u64,
+    strategy: u8,
+    underlying_mint: Pubkey,
+    share_mint: Pubkey,
+    vault_token_account: Pubkey,
+    authority: Pubkey,
+    total_assets: u64,
+    total_shares: u64,
+    last_harvest: i64,
+    last_rebalance: i64,
+    management_fee_bps: u16,
+    performance_fee_bps: u16,
+    high_water_mark: u64,
+    accrued_management_fees: u64,
+    accrued_performance_fees: u64,
+    paused: bool,
+    min_deposit: u64,
+    max_leverage_bps: u16,
+    _current_leverage_bps: u16,
+    rebalance_threshold_bps: u16,
+    harvest_cooldown: i64,
+    rebalance_cooldown: i64,
+    strategy_config: LegacyStrategyConfig,
+    bump: u8,
+}
+
+impl LegacyVault {
+    const LEN: usize = 8 + // discriminator
+        8 +  // vault_id
+        1 +  // strategy
+        32 + // underlying_mint
+        32 + // share_mint
+        32 + // vault_token_account
+        32 + // authority
+        8 +  // total_assets
+        8 +  // total_shares
+        8 +  // last_harvest
+        8 +  // last_rebalance
+        2 +  // management_fee_bps
+        2 +  // performance_fee_bps
+        8 +  // high_water_mark
+        8 +  // accrued_management_fees
+        8 +  // accrued_performance_fees
+        1 +  // paused
+        8 +  // min_deposit
+        2 +  // max_leverage_bps
+        2 +  // current_leverage_bps
+        2 +  // rebalance_threshold_bps
+        8 +  // harvest_cooldown
+        8 +  // rebalance_cooldown
+        LegacyStrategyConfig::LEN + // strategy_config
+        1;   // bump
+}
+
+/// Strategy config layout written by the original program
+#[derive(AnchorDeserialize)]
+struct LegacyStrategyConfig {
+    pool_address: Option<Pubkey>,
+    lending_protocol: Option<Pubkey>,
+    collateral_factor_bps: Option<u16>,
+    hedge_position: Option<Pubkey>,
+    target_allocations: [u16; 4],
+    current_allocations: [u16; 4],
+    oracle_price_feed: Option<Pubkey>,
+    liquidation_threshold_bps: Option<u16>,
+    _reserved: [u8; 64],
+}
+
+impl LegacyStrategyConfig {
+    const LEN: usize = 1 + 32 + // pool_address (Option<Pubkey>)
+        1 + 32 + // lending_protocol (Option<Pubkey>)
+        1 + 2 +  // collateral_factor_bps (Option<u16>)
+        1 + 32 + // hedge_position (Option<Pubkey>)
+        4 * 2 +  // target_allocations [u16; 4]
+        4 * 2 +  // current_allocations [u16; 4]
+        1 + 32 + // oracle_price_feed (Option<Pubkey>)
+        1 + 2 +  // liquidation_threshold_bps (Option<u16>)
+        64;      // reserved
+}
+
+/// Migrate a vault created by the original program to the current account layout
+/// The account is reallocated to `Vault::LEN` (the authority pays the extra rent)
+/// and rewritten with its existing values; fields added since take the defaults
+/// `initialize_vault` uses. The original program had no user positions, so vaults
+/// are the only accounts to migrate
+pub fn migrate_vault(ctx: Context<MigrateVault>, vault_id: u64) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    
+    require!(vault_info.owner == &crate::ID, VaultError::NotLegacyVault);
+    require!(
+        vault_info.data_len() == LegacyVault::LEN,
+        VaultError::NotLegacyVault
+    );
+    
+    let legacy = {
+        let data = vault_info.try_borrow_data()?;
+        require!(
+            data[..8] == Vault::DISCRIMINATOR,
+            VaultError::NotLegacyVault
+        );
+        LegacyVault::deserialize(&mut &data[8..])?
+    };
+    
+    require!(
+        ctx.accounts.authority.key() == legacy.authority,
+        VaultError::Unauthorized
+    );
+    
+    // Authority tops up the rent for the larger layout
+    let required = Rent::get()?.minimum_balance(Vault::LEN);
+    let shortfall = required.saturating_sub(vault_info.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: vault_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    vault_info.realloc(Vault::LEN, true)?;
+    
+    let legacy_config = legacy.strategy_config;
+    let vault = Vault {
+        vault_id: legacy.vault_id,
+        strategy: legacy.strategy,
+        underlying_mint: legacy.underlying_mint,
+        share_mint: legacy.share_mint,
+        vault_token_account: legacy.vault_token_account,
+        authority: legacy.authority,
+        total_assets: legacy.total_assets,
+        total_shares: legacy.total_shares,
+        last_harvest: legacy.last_harvest,
+        last_rebalance: legacy.last_rebalance,
+        management_fee_bps: legacy.management_fee_bps,
+        performance_fee_bps: legacy.performance_fee_bps,
+        high_water_mark: legacy.high_water_mark,
+        accrued_management_fees: legacy.accrued_management_fees,
+        accrued_performance_fees: legacy.accrued_performance_fees,
+        paused: legacy.paused,
+        min_deposit: legacy.min_deposit,
+        max_leverage_bps: legacy.max_leverage_bps,
+        current_leverage_bps: 10000, // No positions yet: derived from the totals from now on
+        rebalance_threshold_bps: legacy.rebalance_threshold_bps,
+        harvest_cooldown: legacy.harvest_cooldown,
+        rebalance_cooldown: legacy.rebalance_cooldown,
+        harvest_bounty_bps: 0,
+        locked_profit: 0,
+        profit_unlock_duration: 21600, // 6 hours default
+        deposits_paused: false,
+        max_loss_report_bps: 1000,
+        loss_pause_threshold_bps: 500,
+        reserve_token_account: Pubkey::default(),
+        reserve_fee_bps: 0,
+        pending_reserve_withdrawal: 0,
+        reserve_withdrawal_unlock_ts: 0,
+        total_collateral: 0,
+        total_debt: 0,
+        hedge_size: 0,
+        net_delta: 0,
+        token_b_mint: Pubkey::default(),
+        vault_token_b_account: Pubkey::default(),
+        total_assets_b: 0,
+        lst_exchange_rate: 0,
+        lock_boost_bps: 0,
+        early_unlock_penalty_bps: 0,
+        total_locked_shares: 0,
+        total_lock_weight: 0,
+        acc_boost_per_weight: 0,
+        strategy_config: StrategyConfig {
+            pool_address: legacy_config.pool_address,
+            lending_protocol: legacy_config.lending_protocol,
+            collateral_factor_bps: legacy_config.collateral_factor_bps,
+            hedge_position: legacy_config.hedge_position,
+            target_allocations: legacy_config.target_allocations,
+            current_allocations: legacy_config.current_allocations,
+            oracle_price_feed: legacy_config.oracle_price_feed,
+            liquidation_threshold_bps: legacy_config.liquidation_threshold_bps,
+            ..StrategyConfig::default()
+        },
+        bump: legacy.bump,
+    };
+    
+    let mut data = vault_info.try_borrow_mut_data()?;
+    vault.try_serialize(&mut &mut data[..])?;
+    
+    msg!("Migrated vault {} to the current layout", vault_id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(vault_id: u64)]
+pub struct MigrateVault<'info> {
+    /// CHECK: Vault in the original layout, checked and deserialized by hand
+    #[account(
+        mut,
+        seeds = [b"vault", vault_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Vault authority, paying the rent for the larger layout
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -5,6 +5,18 @@ pub mod harvest;
 pub mod rebalance;
 pub mod liquidate;
 pub mod pause;
+pub mod report_loss;
+pub mod reserve;
+pub mod position;
+pub mod hedge;
+pub mod clmm;
+pub mod pair;
+pub mod share_metadata;
+pub mod native_sol;
+pub mod liquid_staking;
+pub mod lock;
+pub mod rewards;
+pub mod migrate;
 
 pub use initialize::*;
 pub use deposit::*;
@@ -13,3 +25,15 @@ pub use harvest::*;
 pub use rebalance::*;
 pub use liquidate::*;
 pub use pause::*;
+pub use report_loss::*;
+pub use reserve::*;
+pub use position::*;
+pub use hedge::*;
+pub use clmm::*;
+pub use pair::*;
+pub use share_metadata::*;
+pub use native_sol::*;
+pub use liquid_staking::*;
+pub use lock::*;
+pub use rewards::*;
+pub use migrate::*;
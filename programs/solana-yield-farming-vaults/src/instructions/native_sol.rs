@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token::{self, spl_token, CloseAccount, SyncNative, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::VaultError;
+use super::deposit::{deposit_into_vault, DepositAccounts};
+use super::withdraw::{withdraw_from_vault, WithdrawAccounts};
+
+/// Deposit native SOL into a wrapped-SOL vault and receive shares
+/// The lamports are wrapped in a temporary WSOL account owned by the user,
+/// deposited like any other token and the temporary account is closed again
+pub fn deposit_sol(
+    ctx: Context<DepositSol>,
+    lamports: u64,
+) -> Result<()> {
+    // Wrap: fund the temporary WSOL account and sync its token balance
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user.to_account_info(),
+        to: ctx.accounts.temp_wsol_account.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, lamports)?;
+    
+    let cpi_accounts = SyncNative {
+        account: ctx.accounts.temp_wsol_account.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::sync_native(cpi_ctx)?;
+    
+    // Move the wrapped SOL into the vault and mint shares for it
+    let source = ctx.accounts.temp_wsol_account.to_account_info();
+    deposit_into_vault(
+        DepositAccounts {
+            vault: &mut ctx.accounts.vault,
+            vault_token_account: &mut ctx.accounts.vault_token_account,
+            underlying_mint: &ctx.accounts.underlying_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            share_mint: &ctx.accounts.share_mint,
+            user_share_account: &ctx.accounts.user_share_account,
+            share_token_program: &ctx.accounts.share_token_program,
+            user: &ctx.accounts.user,
+            user_position: &ctx.accounts.user_position,
+            user_lock: &ctx.accounts.user_lock,
+            reward_distributor: &ctx.accounts.reward_distributor,
+            user_rewards: &ctx.accounts.user_rewards,
+        },
+        source,
+        lamports,
+    )?;
+    
+    // Close the emptied temporary account, returning its rent to the user
+    close_temp_wsol_account(
+        &ctx.accounts.temp_wsol_account,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// Withdraw native SOL from a wrapped-SOL vault by burning shares
+/// The vault pays WSOL into a temporary account that is closed straight
+/// to the user, unwrapping it into lamports
+pub fn withdraw_sol(
+    ctx: Context<WithdrawSol>,
+    shares: u64,
+) -> Result<()> {
+    // Burn shares and pay out WSOL to the temporary account
+    let destination = ctx.accounts.temp_wsol_account.to_account_info();
+    withdraw_from_vault(
+        WithdrawAccounts {
+            vault: &mut ctx.accounts.vault,
+            vault_token_account: &ctx.accounts.vault_token_account,
+            underlying_mint: &ctx.accounts.underlying_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            share_mint: &ctx.accounts.share_mint,
+            user_share_account: &ctx.accounts.user_share_account,
+            share_token_program: &ctx.accounts.share_token_program,
+            user: &ctx.accounts.user,
+            user_position: &ctx.accounts.user_position,
+            user_lock: &ctx.accounts.user_lock,
+            reward_distributor: &ctx.accounts.reward_distributor,
+            user_rewards: &ctx.accounts.user_rewards,
+        },
+        destination,
+        shares,
+    )?;
+    
+    // Unwrap: closing a WSOL account releases its balance and rent as lamports
+    close_temp_wsol_account(
+        &ctx.accounts.temp_wsol_account,
+        &ctx.accounts.user,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// Close a user's temporary WSOL account, sending its lamports to the user
+fn close_temp_wsol_account<'info>(
+    temp_wsol_account: &InterfaceAccount<'info, TokenAccount>,
+    user: &Signer<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: temp_wsol_account.to_account_info(),
+        destination: user.to_account_info(),
+        authority: user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::close_account(cpi_ctx)
+}
+
+#[derive(Accounts)]
+pub struct DepositSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        constraint = vault.underlying_mint == spl_token::native_mint::ID @ VaultError::NotNativeSolVault
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Temporary WSOL account, created and closed within the instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"wsol", vault.key().as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub temp_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// SPL Token, which owns the native mint
+    pub token_program: Program<'info, Token>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Depositor's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump,
+        constraint = vault.underlying_mint == spl_token::native_mint::ID @ VaultError::NotNativeSolVault
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Temporary WSOL account, created and closed within the instruction
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"wsol", vault.key().as_ref(), user.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub temp_wsol_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// SPL Token, which owns the native mint
+    pub token_program: Program<'info, Token>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Withdrawer's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,
+}
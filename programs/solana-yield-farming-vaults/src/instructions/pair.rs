@@ -0,0 +1,685 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token::accessor;
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::adapters::{swap, SwapAccounts};
+use crate::utils::{
+    calculate_assets_from_shares, calculate_locked_profit, calculate_matched_pair_amounts,
+    calculate_min_swap_output, calculate_pair_value, calculate_shares_to_mint,
+    convert_at_oracle_price, get_oracle_price, validate_oracle_price,
+};
+use crate::events::{PairDepositEvent, PairWithdrawEvent};
+use super::position::{held_shares, track_deposit, track_withdrawal};
+use super::rewards::checkpoint_rewards;
+
+/// Turn an empty vault into a dual-asset (token A / token B) vault
+/// Token A is the vault's underlying mint; share pricing values both legs
+/// in token A units using the two oracle feeds
+pub fn initialize_token_b(ctx: Context<InitializeTokenB>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can add the second leg
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(vault.total_shares == 0, VaultError::InvalidStrategy);
+    require!(
+        vault.token_b_mint == Pubkey::default(),
+        VaultError::DualAssetVault
+    );
+    require!(
+        ctx.accounts.token_b_mint.key() != vault.underlying_mint,
+        VaultError::InvalidMint
+    );
+    
+    vault.token_b_mint = ctx.accounts.token_b_mint.key();
+    vault.vault_token_b_account = ctx.accounts.vault_token_b_account.key();
+    vault.total_assets_b = 0;
+    vault.strategy_config.token_b_oracle_price_feed = Some(ctx.accounts.token_b_oracle.key());
+    
+    msg!("Vault {} is now dual-asset with token B {}", 
+         vault.vault_id, vault.token_b_mint);
+    
+    Ok(())
+}
+
+/// Deposit both tokens into a dual-asset vault and receive shares
+/// Amounts are trimmed to the vault's current token ratio. With
+/// `swap_to_ratio`, both amounts are taken in full and the over-supplied
+/// leg is swapped into the other through the swap adapter instead
+pub fn deposit_pair(
+    ctx: Context<DepositPair>,
+    amount_a: u64,
+    amount_b: u64,
+    swap_to_ratio: bool,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &ctx.accounts.vault;
+    
+    require!(!vault.paused, VaultError::VaultPaused);
+    require!(!vault.deposits_paused, VaultError::DepositsPaused);
+    require!(
+        vault.token_b_mint != Pubkey::default(),
+        VaultError::NotDualAssetVault
+    );
+    
+    let (price_a, price_b) = get_pair_prices(vault)?;
+    let decimals_a = ctx.accounts.underlying_mint.decimals;
+    let decimals_b = ctx.accounts.token_b_mint.decimals;
+    
+    // Without a swap only the ratio-matched amounts are pulled from the user
+    let (transfer_a, transfer_b) = if swap_to_ratio {
+        (amount_a, amount_b)
+    } else {
+        calculate_matched_pair_amounts(amount_a, amount_b, vault.total_assets, vault.total_assets_b)?
+    };
+    require!(transfer_a > 0 || transfer_b > 0, VaultError::DepositTooSmall);
+    
+    // Credit only what arrived (Token-2022 transfer fees are withheld from the recipient)
+    let mut received = [0u64; 2];
+    for (i, (from, to, mint, amount)) in [
+        (&ctx.accounts.user_token_account, &ctx.accounts.vault_token_account, &ctx.accounts.underlying_mint, transfer_a),
+        (&ctx.accounts.user_token_b_account, &ctx.accounts.vault_token_b_account, &ctx.accounts.token_b_mint, transfer_b),
+    ].into_iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        let to_info = to.to_account_info();
+        let balance_before = accessor::amount(&to_info)?;
+    
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to_info.clone(),
+            authority: ctx.accounts.user.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    
+        received[i] = accessor::amount(&to_info)?
+            .checked_sub(balance_before)
+            .ok_or(VaultError::MathOverflow)?;
+    }
+    let [received_a, received_b] = received;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let mut credited_a = received_a;
+    let mut credited_b = received_b;
+    let mut swapped_in = 0;
+    let mut swapped_a_to_b = false;
+    
+    if swap_to_ratio {
+        // Target token B share of value: the vault's current split, or 50/50 when empty
+        let deposit_value = calculate_pair_value(
+            received_a, received_b, price_a, decimals_a, price_b, decimals_b,
+        )?;
+        let vault_value = calculate_pair_value(
+            vault.total_assets, vault.total_assets_b, price_a, decimals_a, price_b, decimals_b,
+        )?;
+        let target_value_b = if vault_value == 0 {
+            deposit_value / 2
+        } else {
+            let vault_value_b = convert_at_oracle_price(
+                vault.total_assets_b, price_b, decimals_b, price_a, decimals_a,
+            )?;
+            ((deposit_value as u128)
+                .checked_mul(vault_value_b as u128)
+                .ok_or(VaultError::MathOverflow)?
+                / vault_value as u128) as u64
+        };
+        let value_b = convert_at_oracle_price(received_b, price_b, decimals_b, price_a, decimals_a)?;
+    
+        // Amount of the over-supplied leg to swap, in that leg's units
+        let (amount_in, a_to_b) = if value_b < target_value_b {
+            (target_value_b - value_b, true)
+        } else {
+            let excess = convert_at_oracle_price(
+                value_b - target_value_b, price_a, decimals_a, price_b, decimals_b,
+            )?;
+            (excess, false)
+        };
+    
+        if amount_in > 0 {
+            let (price_in, decimals_in, price_out, decimals_out) = if a_to_b {
+                (price_a, decimals_a, price_b, decimals_b)
+            } else {
+                (price_b, decimals_b, price_a, decimals_a)
+            };
+            let min_amount_out = calculate_min_swap_output(
+                amount_in,
+                price_in,
+                decimals_in,
+                price_out,
+                decimals_out,
+                vault.strategy_config.max_swap_slippage_bps,
+            )?;
+    
+            let swap_program = ctx.accounts.swap_program
+                .as_ref()
+                .ok_or(VaultError::InvalidSwapProgram)?;
+            require!(
+                vault.strategy_config.swap_program == Some(swap_program.key()),
+                VaultError::InvalidSwapProgram
+            );
+            let swap_pool = ctx.accounts.swap_pool
+                .as_ref()
+                .ok_or(VaultError::InvalidSwapProgram)?;
+            let pool_source = ctx.accounts.swap_pool_source
+                .as_ref()
+                .ok_or(VaultError::InvalidSwapProgram)?;
+            let pool_destination = ctx.accounts.swap_pool_destination
+                .as_ref()
+                .ok_or(VaultError::InvalidSwapProgram)?;
+    
+            let vault_a_info = ctx.accounts.vault_token_account.to_account_info();
+            let vault_b_info = ctx.accounts.vault_token_b_account.to_account_info();
+            let (source, destination) = if a_to_b {
+                (vault_a_info, vault_b_info)
+            } else {
+                (vault_b_info, vault_a_info)
+            };
+    
+            let balance_before = accessor::amount(&destination)?;
+    
+            swap(
+                &swap_program.to_account_info(),
+                SwapAccounts {
+                    pool: swap_pool.to_account_info(),
+                    pool_source: pool_source.to_account_info(),
+                    pool_destination: pool_destination.to_account_info(),
+                    source,
+                    destination: destination.clone(),
+                    authority: vault_info.clone(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                amount_in,
+                min_amount_out,
+                signer,
+            )?;
+    
+            // Credit only what actually arrived in the vault
+            let amount_out = accessor::amount(&destination)?
+                .checked_sub(balance_before)
+                .ok_or(VaultError::MathOverflow)?;
+            require!(amount_out >= min_amount_out, VaultError::SlippageExceeded);
+    
+            if a_to_b {
+                credited_a = credited_a.checked_sub(amount_in).ok_or(VaultError::MathOverflow)?;
+                credited_b = credited_b.checked_add(amount_out).ok_or(VaultError::MathOverflow)?;
+            } else {
+                credited_b = credited_b.checked_sub(amount_in).ok_or(VaultError::MathOverflow)?;
+                credited_a = credited_a.checked_add(amount_out).ok_or(VaultError::MathOverflow)?;
+            }
+            swapped_in = amount_in;
+            swapped_a_to_b = a_to_b;
+        }
+    }
+    
+    // Price shares on the oracle value of both legs (in token A units)
+    let deposit_value = calculate_pair_value(
+        credited_a, credited_b, price_a, decimals_a, price_b, decimals_b,
+    )?;
+    let vault_value = calculate_pair_value(
+        vault.total_assets, vault.total_assets_b, price_a, decimals_a, price_b, decimals_b,
+    )?;
+    require!(deposit_value >= vault.min_deposit, VaultError::DepositTooSmall);
+    
+    let locked_profit = calculate_locked_profit(vault, clock.unix_timestamp)?;
+    let shares_to_mint = calculate_shares_to_mint(
+        deposit_value,
+        vault_value,
+        locked_profit,
+        vault.total_shares,
+    )?;
+    require!(shares_to_mint > 0, VaultError::MathOverflow);
+    
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        to: ctx.accounts.user_share_account.to_account_info(),
+        authority: vault_info,
+    };
+    let cpi_program = ctx.accounts.share_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::mint_to(cpi_ctx, shares_to_mint)?;
+    
+    // Minted shares start earning emission rewards
+    checkpoint_rewards(
+        &ctx.accounts.reward_distributor,
+        &ctx.accounts.user_rewards,
+        shares_to_mint,
+        0,
+    )?;
+    
+    // Cost basis is the deposit's value in token A units, token B included
+    track_deposit(
+        &ctx.accounts.user_position.to_account_info(),
+        held_shares(&ctx.accounts.user_share_account, &ctx.accounts.user_lock.to_account_info())?,
+        deposit_value,
+        shares_to_mint,
+        clock.unix_timestamp,
+    )?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.total_assets = vault.total_assets
+        .checked_add(credited_a)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_assets_b = vault.total_assets_b
+        .checked_add(credited_b)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_shares = vault.total_shares
+        .checked_add(shares_to_mint)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    emit!(PairDepositEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        amount_a: received_a,
+        amount_b: received_b,
+        swapped_in,
+        swapped_a_to_b,
+        deposit_value,
+        shares_minted: shares_to_mint,
+        total_assets: vault.total_assets,
+        total_assets_b: vault.total_assets_b,
+        total_shares: vault.total_shares,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Deposited {} token A and {} token B, minted {} shares", 
+         received_a, received_b, shares_to_mint);
+    
+    Ok(())
+}
+
+/// Withdraw both tokens from a dual-asset vault pro rata by burning shares
+pub fn withdraw_pair(
+    ctx: Context<WithdrawPair>,
+    shares: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    require!(!vault.paused, VaultError::VaultPaused);
+    require!(
+        vault.token_b_mint != Pubkey::default(),
+        VaultError::NotDualAssetVault
+    );
+    require!(
+        ctx.accounts.user_share_account.amount >= shares,
+        VaultError::InsufficientFunds
+    );
+    
+    // Locked profit is harvested into token A and stays until it vests
+    let locked_profit = calculate_locked_profit(vault, clock.unix_timestamp)?;
+    let amount_a = calculate_assets_from_shares(
+        shares,
+        vault.total_assets,
+        locked_profit,
+        vault.total_shares,
+    )?;
+    let amount_b = calculate_assets_from_shares(
+        shares,
+        vault.total_assets_b,
+        0,
+        vault.total_shares,
+    )?;
+    
+    require!(
+        amount_a <= ctx.accounts.vault_token_account.amount
+            && amount_b <= ctx.accounts.vault_token_b_account.amount,
+        VaultError::InsufficientFunds
+    );
+    
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.share_mint.to_account_info(),
+        from: ctx.accounts.user_share_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.share_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::burn(cpi_ctx, shares)?;
+    
+    // Burned shares stop earning emission rewards
+    checkpoint_rewards(
+        &ctx.accounts.reward_distributor,
+        &ctx.accounts.user_rewards,
+        0,
+        shares,
+    )?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    for (from, to, mint, amount) in [
+        (&ctx.accounts.vault_token_account, &ctx.accounts.user_token_account, &ctx.accounts.underlying_mint, amount_a),
+        (&ctx.accounts.vault_token_b_account, &ctx.accounts.user_token_b_account, &ctx.accounts.token_b_mint, amount_b),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: vault_info.clone(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)?;
+    }
+    
+    vault.total_assets = vault.total_assets
+        .checked_sub(amount_a)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_assets_b = vault.total_assets_b
+        .checked_sub(amount_b)
+        .ok_or(VaultError::MathOverflow)?;
+    vault.total_shares = vault.total_shares
+        .checked_sub(shares)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Realize PnL on both legs valued in token A units. Only a tracked position
+    // needs the oracles; untracked withdrawals never depend on them
+    if ctx.accounts.user_position.owner == &crate::ID {
+        let (price_a, price_b) = get_pair_prices(vault)?;
+        let withdrawn_value = calculate_pair_value(
+            amount_a,
+            amount_b,
+            price_a,
+            ctx.accounts.underlying_mint.decimals,
+            price_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+        track_withdrawal(
+            &ctx.accounts.user_position.to_account_info(),
+            held_shares(&ctx.accounts.user_share_account, &ctx.accounts.user_lock.to_account_info())?,
+            withdrawn_value,
+            shares,
+            clock.unix_timestamp,
+        )?;
+    }
+    
+    emit!(PairWithdrawEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        amount_a,
+        amount_b,
+        shares_burned: shares,
+        total_assets: vault.total_assets,
+        total_assets_b: vault.total_assets_b,
+        total_shares: vault.total_shares,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Withdrew {} token A and {} token B, burned {} shares", 
+         amount_a, amount_b, shares);
+    
+    Ok(())
+}
+
+/// Oracle prices of token A (underlying) and token B
+pub(crate) fn get_pair_prices(vault: &Vault) -> Result<(i64, i64)> {
+    let oracle_a = vault.strategy_config
+        .oracle_price_feed
+        .ok_or(VaultError::InvalidOracle)?;
+    let oracle_b = vault.strategy_config
+        .token_b_oracle_price_feed
+        .ok_or(VaultError::InvalidOracle)?;
+    
+    require!(
+        validate_oracle_price(&oracle_a, 300)? && validate_oracle_price(&oracle_b, 300)?,
+        VaultError::StaleOraclePrice
+    );
+    
+    Ok((get_oracle_price(&oracle_a)?, get_oracle_price(&oracle_b)?))
+}
+
+#[derive(Accounts)]
+pub struct InitializeTokenB<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    pub token_b_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = vault_token_b_account.mint == token_b_mint.key(),
+        constraint = vault_token_b_account.owner == vault.key()
+    )]
+    pub vault_token_b_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Pyth price feed for token B, validated when read
+    pub token_b_oracle: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DepositPair<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_b_account.key() == vault.vault_token_b_account
+    )]
+    pub vault_token_b_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == vault.underlying_mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_token_b_account.mint == vault.token_b_mint,
+        constraint = user_token_b_account.owner == user.key()
+    )]
+    pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = token_b_mint.key() == vault.token_b_mint
+    )]
+    pub token_b_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Token program of both legs (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Swap adapter program, validated against strategy_config.swap_program
+    /// (swap accounts are only required with `swap_to_ratio`)
+    pub swap_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Swap pool, validated by the swap program
+    #[account(mut)]
+    pub swap_pool: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault receiving the swapped-out leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_source: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Pool vault paying out the other leg, validated by the swap program
+    #[account(mut)]
+    pub swap_pool_destination: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,    
+    /// CHECK: User's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPair<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.key() == vault.vault_token_account
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_b_account.key() == vault.vault_token_b_account
+    )]
+    pub vault_token_b_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_token_account.mint == vault.underlying_mint,
+        constraint = user_token_account.owner == user.key()
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_token_b_account.mint == vault.token_b_mint,
+        constraint = user_token_b_account.owner == user.key()
+    )]
+    pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        constraint = token_b_mint.key() == vault.token_b_mint
+    )]
+    pub token_b_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Token program of both legs (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,    
+    /// CHECK: User's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+}
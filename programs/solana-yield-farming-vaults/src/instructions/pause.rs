@@ -21,7 +21,7 @@ pub fn pause_vault(ctx: Context<PauseVault>) -> Result<()> {
     Ok(())
 }
 
-/// Unpause a vault
+/// Unpause a vault (also re-enables deposits paused after a reported loss)
 pub fn unpause_vault(ctx: Context<UnpauseVault>) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     
@@ -31,9 +31,10 @@ pub fn unpause_vault(ctx: Context<UnpauseVault>) -> Result<()> {
         VaultError::Unauthorized
     );
     
-    require!(vault.paused, VaultError::VaultPaused);
+    require!(vault.paused || vault.deposits_paused, VaultError::VaultPaused);
     
     vault.paused = false;
+    vault.deposits_paused = false;
     
     msg!("Vault {} unpaused", vault.vault_id);
     
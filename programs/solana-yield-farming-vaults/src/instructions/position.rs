@@ -0,0 +1,365 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::adapters::lending::{self, InitObligationAccounts};
+use crate::utils::{
+    calculate_assets_from_shares, calculate_avg_entry_price, calculate_cost_basis,
+    calculate_locked_profit, calculate_pair_value, calculate_share_price,
+};
+use super::pair::get_pair_prices;
+
+/// Create a user's position account
+/// For leveraged strategies this also opens the position's obligation on the
+/// lending market, owned by the vault PDA
+pub fn initialize_user_position(
+    ctx: Context<InitializeUserPosition>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    let mut obligation_key = Pubkey::default();
+    
+    if let Some(lending_protocol) = vault.strategy_config.lending_protocol {
+        let lending_program = ctx.accounts.lending_program
+            .as_ref()
+            .ok_or(VaultError::InvalidLendingAccount)?;
+        let lending_market = ctx.accounts.lending_market
+            .as_ref()
+            .ok_or(VaultError::InvalidLendingAccount)?;
+        let obligation = ctx.accounts.obligation
+            .as_ref()
+            .ok_or(VaultError::InvalidLendingAccount)?;
+    
+        require!(
+            lending_program.key() == lending_protocol,
+            VaultError::InvalidLendingAccount
+        );
+        require!(
+            Some(lending_market.key()) == vault.strategy_config.lending_market,
+            VaultError::InvalidLendingAccount
+        );
+    
+        let vault_id_bytes = vault.vault_id.to_le_bytes();
+        let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+        let signer = &[&seeds[..]];
+    
+        lending::init_obligation(
+            &lending_program.to_account_info(),
+            InitObligationAccounts {
+                market: lending_market.to_account_info(),
+                obligation: obligation.to_account_info(),
+                owner: vault.to_account_info(),
+                seed: ctx.accounts.user_position.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer,
+        )?;
+    
+        obligation_key = obligation.key();
+    }
+    
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.user = ctx.accounts.user.key();
+    user_position.vault = ctx.accounts.vault.key();
+    user_position.shares = 0;
+    user_position.leverage_bps = 10000; // 1x
+    user_position.collateral = 0;
+    user_position.debt = 0;
+    user_position.obligation = obligation_key;
+    user_position.liquidation_auction_start = 0;
+    user_position.last_interaction = clock.unix_timestamp;
+    user_position.total_deposited = 0;
+    user_position.total_withdrawn = 0;
+    user_position.avg_entry_share_price = 0;
+    user_position.realized_pnl = 0;
+    user_position.bump = ctx.bumps.user_position;
+    
+    msg!("Initialized position for {} in vault {}",
+         user_position.user, ctx.accounts.vault.vault_id);
+    
+    Ok(())
+}
+
+/// Record a deposit on the user's position PDA. The PDA is always passed,
+/// so once a position is opened every deposit is tracked; before that it is
+/// uninitialized and nothing is recorded
+pub fn track_deposit(
+    user_position: &AccountInfo,
+    held_shares: u64,
+    assets: u64,
+    shares: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if user_position.owner != &crate::ID {
+        return Ok(());
+    }
+    
+    let mut position = UserPosition::try_deserialize(&mut &user_position.try_borrow_data()?[..])?;
+    cap_tracked_shares(&mut position, held_shares);
+    record_deposit(&mut position, assets, shares, timestamp)?;
+    position.try_serialize(&mut &mut user_position.try_borrow_mut_data()?[..])
+}
+
+/// Record a withdrawal on the user's position PDA, if the user has opened one
+pub fn track_withdrawal(
+    user_position: &AccountInfo,
+    held_shares: u64,
+    assets: u64,
+    shares: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if user_position.owner != &crate::ID {
+        return Ok(());
+    }
+    
+    let mut position = UserPosition::try_deserialize(&mut &user_position.try_borrow_data()?[..])?;
+    cap_tracked_shares(&mut position, held_shares);
+    record_withdrawal(&mut position, assets, shares, timestamp)?;
+    position.try_serialize(&mut &mut user_position.try_borrow_mut_data()?[..])
+}
+
+/// Shares a user holds in a vault before this instruction's mint or burn:
+/// the share account passed in plus the shares in their lock escrow
+pub fn held_shares(
+    user_share_account: &InterfaceAccount<TokenAccount>,
+    user_lock: &AccountInfo,
+) -> Result<u64> {
+    let locked_shares = if user_lock.owner == &crate::ID {
+        UserLock::try_deserialize(&mut &user_lock.try_borrow_data()?[..])?.shares
+    } else {
+        0
+    };
+    
+    user_share_account.amount
+        .checked_add(locked_shares)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Drop tracked shares the user no longer holds. Shares transferred out
+/// take their cost basis with them, at the position's average entry price
+pub fn cap_tracked_shares(position: &mut UserPosition, held_shares: u64) {
+    position.shares = position.shares.min(held_shares);
+    if position.shares == 0 {
+        position.avg_entry_share_price = 0;
+    }
+}
+
+/// Record a deposit on the user's position: shares bought at the deposit's
+/// share price move the average entry price
+pub fn record_deposit(
+    position: &mut UserPosition,
+    assets: u64,
+    shares: u64,
+    timestamp: i64,
+) -> Result<()> {
+    position.avg_entry_share_price = calculate_avg_entry_price(
+        position.shares,
+        position.avg_entry_share_price,
+        shares,
+        calculate_share_price(assets, shares)?,
+    )?;
+    position.shares = position.shares
+        .checked_add(shares)
+        .ok_or(VaultError::MathOverflow)?;
+    position.total_deposited = position.total_deposited
+        .checked_add(assets)
+        .ok_or(VaultError::MathOverflow)?;
+    position.last_interaction = timestamp;
+    
+    Ok(())
+}
+
+/// Record a withdrawal on the user's position, realizing PnL against the
+/// average entry price. Shares received by transfer carry no cost basis,
+/// so only the position's tracked shares and their share of the assets are costed
+pub fn record_withdrawal(
+    position: &mut UserPosition,
+    assets: u64,
+    shares: u64,
+    timestamp: i64,
+) -> Result<()> {
+    let tracked_shares = shares.min(position.shares);
+    let tracked_assets = if shares > 0 {
+        (assets as u128)
+            .checked_mul(tracked_shares as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(shares as u128)
+            .ok_or(VaultError::MathOverflow)? as u64
+    } else {
+        0
+    };
+    let cost = calculate_cost_basis(tracked_shares, position.avg_entry_share_price)?;
+    let pnl = (tracked_assets as i128)
+        .checked_sub(cost as i128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    position.realized_pnl = i64::try_from(
+        (position.realized_pnl as i128)
+            .checked_add(pnl)
+            .ok_or(VaultError::MathOverflow)?,
+    )
+    .map_err(|_| VaultError::MathOverflow)?;
+    position.shares = position.shares
+        .checked_sub(tracked_shares)
+        .ok_or(VaultError::MathOverflow)?;
+    if position.shares == 0 {
+        position.avg_entry_share_price = 0;
+    }
+    position.total_withdrawn = position.total_withdrawn
+        .checked_add(assets)
+        .ok_or(VaultError::MathOverflow)?;
+    position.last_interaction = timestamp;
+    
+    Ok(())
+}
+
+/// Return a position's current value and PnL (read-only view)
+/// Value uses the same share price as withdraw, so unvested profit is excluded
+pub fn get_position_pnl(ctx: Context<GetPositionPnl>) -> Result<PositionPnl> {
+    let vault = &ctx.accounts.vault;
+    let mut position = (*ctx.accounts.user_position).clone();
+    let clock = Clock::get()?;
+    
+    cap_tracked_shares(
+        &mut position,
+        held_shares(&ctx.accounts.user_share_account, &ctx.accounts.user_lock.to_account_info())?,
+    );
+    
+    let current_value = if vault.total_shares > 0 {
+        let locked_profit = calculate_locked_profit(vault, clock.unix_timestamp)?;
+        let assets_a = calculate_assets_from_shares(
+            position.shares,
+            vault.total_assets,
+            locked_profit,
+            vault.total_shares,
+        )?;
+    
+        // Dual-asset positions are valued in token A units, token B at the oracle price
+        if vault.token_b_mint != Pubkey::default() {
+            let underlying_mint = ctx.accounts.underlying_mint
+                .as_ref()
+                .ok_or(VaultError::InvalidMint)?;
+            let token_b_mint = ctx.accounts.token_b_mint
+                .as_ref()
+                .ok_or(VaultError::InvalidMint)?;
+            let assets_b = calculate_assets_from_shares(
+                position.shares,
+                vault.total_assets_b,
+                0,
+                vault.total_shares,
+            )?;
+            let (price_a, price_b) = get_pair_prices(vault)?;
+            calculate_pair_value(
+                assets_a, assets_b,
+                price_a, underlying_mint.decimals,
+                price_b, token_b_mint.decimals,
+            )?
+        } else {
+            assets_a
+        }
+    } else {
+        0
+    };
+    let cost_basis = calculate_cost_basis(position.shares, position.avg_entry_share_price)?;
+    let unrealized_pnl = i64::try_from(current_value as i128 - cost_basis as i128)
+        .map_err(|_| VaultError::MathOverflow)?;
+    
+    msg!("Position value {}, cost basis {}, unrealized PnL {}, realized PnL {}",
+         current_value, cost_basis, unrealized_pnl, position.realized_pnl);
+    
+    Ok(PositionPnl {
+        shares: position.shares,
+        current_value,
+        cost_basis,
+        avg_entry_share_price: position.avg_entry_share_price,
+        unrealized_pnl,
+        realized_pnl: position.realized_pnl,
+        total_deposited: position.total_deposited,
+        total_withdrawn: position.total_withdrawn,
+    })
+}
+
+/// Position value and PnL returned by `get_position_pnl` (vault accounting units)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PositionPnl {
+    pub shares: u64,
+    pub current_value: u64,
+    pub cost_basis: u64,
+    pub avg_entry_share_price: u64,
+    pub unrealized_pnl: i64,
+    pub realized_pnl: i64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserPosition<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// CHECK: Lending adapter program (leveraged strategies only), validated in handler
+    pub lending_program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Lending market (leveraged strategies only), validated in handler
+    pub lending_market: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Obligation to open, created and validated by the lending program
+    #[account(mut)]
+    pub obligation: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct GetPositionPnl<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        seeds = [b"user_position", vault.key().as_ref(), user_position.user.as_ref()],
+        bump = user_position.bump
+    )]
+    pub user_position: Account<'info, UserPosition>,
+    
+    /// Position owner's share account; tracked shares are capped at what they still hold
+    #[account(
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user_position.user
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Position owner's share lock PDA, uninitialized if they have no lock
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user_position.user.as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+    
+    /// Underlying mint (dual-asset vaults only)
+    #[account(constraint = underlying_mint.key() == vault.underlying_mint)]
+    pub underlying_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Token B mint (dual-asset vaults only)
+    #[account(constraint = token_b_mint.key() == vault.token_b_mint)]
+    pub token_b_mint: Option<InterfaceAccount<'info, Mint>>,
+}
@@ -86,9 +86,12 @@ pub fn rebalance(
 }
 
 /// Update vault strategy configuration
+/// Runtime state recorded by the strategy instructions (open CLMM/perp positions,
+/// the CLMM range and current allocations) is kept, and the lending, CLMM and perp
+/// venues are locked while positions are open on them
 pub fn update_strategy_config(
     ctx: Context<UpdateStrategyConfig>,
-    strategy_config: StrategyConfig,
+    mut strategy_config: StrategyConfig,
 ) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     
@@ -98,6 +101,104 @@ pub fn update_strategy_config(
         VaultError::Unauthorized
     );
     
+    // Reward swaps may not tolerate more than 100% slippage
+    require!(
+        strategy_config.max_swap_slippage_bps <= 10000,
+        VaultError::SlippageExceeded
+    );
+    
+    // Liquidation bonus between 0% and 20%
+    if let Some(bonus) = strategy_config.liquidation_bonus_bps {
+        require!((10000..=12000).contains(&bonus), VaultError::InvalidFeeConfig);
+    }
+    
+    // Protocol cut is a share of the bonus
+    if let Some(fee) = strategy_config.liquidation_protocol_fee_bps {
+        require!(fee <= 10000, VaultError::InvalidFeeConfig);
+    }
+    
+    // Auction bonus between 0% and 20%, reached over a positive duration
+    if let Some(max_bonus) = strategy_config.auction_max_bonus_bps {
+        require!((10000..=12000).contains(&max_bonus), VaultError::InvalidFeeConfig);
+    }
+    if let Some(duration) = strategy_config.auction_duration {
+        require!(duration > 0, VaultError::InvalidTimestamp);
+    }
+    
+    // Soft deleverage threshold sits above the liquidation threshold
+    if let Some(threshold) = strategy_config.deleverage_threshold_bps {
+        let liquidation_threshold = strategy_config.liquidation_threshold_bps.unwrap_or(11000);
+        require!(threshold > liquidation_threshold, VaultError::InvalidLeverage);
+    }
+    if let Some(target) = strategy_config.deleverage_target_leverage_bps {
+        require!(target >= 10000 && target <= vault.max_leverage_bps, VaultError::InvalidLeverage);
+    }
+    
+    // Keeper fee capped at 1% of repaid debt
+    if let Some(fee) = strategy_config.keeper_fee_bps {
+        require!(fee <= 100, VaultError::InvalidFeeConfig);
+    }
+    
+    // Close factor must allow repaying some debt and at most all of it
+    if let Some(close_factor) = strategy_config.close_factor_bps {
+        require!(close_factor > 0 && close_factor <= 10000, VaultError::InvalidFeeConfig);
+    }
+    
+    // Rehedge band between 0% and 50% of total assets
+    if let Some(band) = strategy_config.rehedge_band_bps {
+        require!(band <= 5000, VaultError::InvalidFeeConfig);
+    }
+    
+    // Cross-asset debt is priced by its own oracle and looped through the swap adapter
+    if strategy_config.debt_mint.is_some_and(|mint| mint != vault.underlying_mint) {
+        require!(
+            strategy_config.debt_oracle_price_feed.is_some(),
+            VaultError::InvalidOracle
+        );
+        require!(
+            strategy_config.swap_program.is_some(),
+            VaultError::InvalidSwapProgram
+        );
+    }
+    
+    let current = &vault.strategy_config;
+    
+    // Leveraged positions hold obligations on the configured lending market
+    if vault.total_collateral > 0 || vault.total_debt > 0 {
+        require!(
+            strategy_config.lending_protocol == current.lending_protocol
+                && strategy_config.lending_market == current.lending_market
+                && strategy_config.debt_mint == current.debt_mint,
+            VaultError::VenueInUse
+        );
+    }
+    
+    // An open CLMM position lives in the configured pool
+    if current.clmm_position.is_some() {
+        require!(
+            strategy_config.clmm_program == current.clmm_program
+                && strategy_config.pool_address == current.pool_address,
+            VaultError::VenueInUse
+        );
+    }
+    
+    // An open hedge lives on the configured perp market
+    if current.hedge_position.is_some() {
+        require!(
+            strategy_config.perp_program == current.perp_program
+                && strategy_config.perp_market == current.perp_market,
+            VaultError::VenueInUse
+        );
+    }
+    
+    // Fields owned by the strategy instructions are never taken from the caller
+    strategy_config.clmm_position = current.clmm_position;
+    strategy_config.tick_lower = current.tick_lower;
+    strategy_config.tick_upper = current.tick_upper;
+    strategy_config.hedge_position = current.hedge_position;
+    strategy_config.current_allocations = current.current_allocations;
+    strategy_config.stake_pool = current.stake_pool;
+    
     vault.strategy_config = strategy_config;
     
     msg!("Updated strategy config for vault {}", vault.vault_id);
@@ -108,12 +209,7 @@ pub fn update_strategy_config(
 /// Update vault parameters (fees, cooldowns, etc.)
 pub fn update_vault_params(
     ctx: Context<UpdateVaultParams>,
-    management_fee_bps: Option<u16>,
-    performance_fee_bps: Option<u16>,
-    harvest_cooldown: Option<i64>,
-    rebalance_cooldown: Option<i64>,
-    rebalance_threshold_bps: Option<u16>,
-    min_deposit: Option<u64>,
+    params: VaultParamsUpdate,
 ) -> Result<()> {
     let vault = &mut ctx.accounts.vault;
     
@@ -123,34 +219,65 @@ pub fn update_vault_params(
         VaultError::Unauthorized
     );
     
-    if let Some(fee) = management_fee_bps {
+    if let Some(fee) = params.management_fee_bps {
         require!(fee <= 1000, VaultError::InvalidFeeConfig);
         vault.management_fee_bps = fee;
     }
     
-    if let Some(fee) = performance_fee_bps {
+    if let Some(fee) = params.performance_fee_bps {
         require!(fee <= 5000, VaultError::InvalidFeeConfig);
         vault.performance_fee_bps = fee;
     }
     
-    if let Some(cooldown) = harvest_cooldown {
+    if let Some(cooldown) = params.harvest_cooldown {
         require!(cooldown >= 0, VaultError::InvalidTimestamp);
         vault.harvest_cooldown = cooldown;
     }
     
-    if let Some(cooldown) = rebalance_cooldown {
+    if let Some(cooldown) = params.rebalance_cooldown {
         require!(cooldown >= 0, VaultError::InvalidTimestamp);
         vault.rebalance_cooldown = cooldown;
     }
     
-    if let Some(threshold) = rebalance_threshold_bps {
+    if let Some(threshold) = params.rebalance_threshold_bps {
         vault.rebalance_threshold_bps = threshold;
     }
     
-    if let Some(min) = min_deposit {
+    if let Some(min) = params.min_deposit {
         vault.min_deposit = min;
     }
     
+    if let Some(bounty) = params.harvest_bounty_bps {
+        // Max 5% of realized rewards
+        require!(bounty <= 500, VaultError::InvalidFeeConfig);
+        vault.harvest_bounty_bps = bounty;
+    }
+    
+    if let Some(duration) = params.profit_unlock_duration {
+        require!(duration >= 0, VaultError::InvalidTimestamp);
+        vault.profit_unlock_duration = duration;
+    }
+    
+    if let Some(max_loss) = params.max_loss_report_bps {
+        require!(max_loss <= 10000, VaultError::InvalidLossReport);
+        vault.max_loss_report_bps = max_loss;
+    }
+    
+    if let Some(threshold) = params.loss_pause_threshold_bps {
+        require!(threshold <= 10000, VaultError::InvalidLossReport);
+        vault.loss_pause_threshold_bps = threshold;
+    }
+    
+    if let Some(fee) = params.reserve_fee_bps {
+        require!(
+            vault.reserve_token_account != Pubkey::default(),
+            VaultError::ReserveNotInitialized
+        );
+        // Max 20% of each harvest
+        require!(fee <= 2000, VaultError::InvalidFeeConfig);
+        vault.reserve_fee_bps = fee;
+    }
+    
     msg!("Updated vault parameters");
     
     Ok(())
@@ -180,6 +307,22 @@ pub struct UpdateStrategyConfig<'info> {
     pub authority: Signer<'info>,
 }
 
+/// Vault parameter changes; `None` leaves the current value untouched
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct VaultParamsUpdate {
+    pub management_fee_bps: Option<u16>,
+    pub performance_fee_bps: Option<u16>,
+    pub harvest_cooldown: Option<i64>,
+    pub rebalance_cooldown: Option<i64>,
+    pub rebalance_threshold_bps: Option<u16>,
+    pub min_deposit: Option<u64>,
+    pub harvest_bounty_bps: Option<u16>,
+    pub profit_unlock_duration: Option<i64>,
+    pub max_loss_report_bps: Option<u16>,
+    pub loss_pause_threshold_bps: Option<u16>,
+    pub reserve_fee_bps: Option<u16>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateVaultParams<'info> {
     #[account(
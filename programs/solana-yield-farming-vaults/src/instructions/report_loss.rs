@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::utils::absorb_loss_with_locked_profit;
+use crate::events::LossReportedEvent;
+use super::reserve::cover_from_reserve;
+
+/// Report a realized strategy loss so it is reflected in share price
+/// Callable by the vault authority or the configured strategy adapter
+/// When `draw_buffer` is set, the insurance reserve and then unvested profit
+/// absorb the loss before it is socialized across shares
+pub fn report_loss(
+    ctx: Context<ReportLoss>,
+    loss_amount: u64,
+    draw_buffer: bool,
+) -> Result<()> {
+    let reporter = ctx.accounts.reporter.key();
+    let clock = Clock::get()?;
+    
+    let vault = &ctx.accounts.vault;
+    
+    // Only vault authority or strategy adapter can report losses
+    require!(
+        reporter == vault.authority || Some(reporter) == vault.strategy_config.strategy_adapter,
+        VaultError::Unauthorized
+    );
+    
+    require!(loss_amount > 0, VaultError::InvalidLossReport);
+    
+    // Bound a single report to a fraction of total assets
+    let max_loss = vault.total_assets
+        .checked_mul(vault.max_loss_report_bps as u64)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    require!(loss_amount <= max_loss, VaultError::InvalidLossReport);
+    
+    // Insurance reserve covers the loss first, only once the report is validated
+    let reserve_covered = match (&ctx.accounts.reserve_token_account, draw_buffer) {
+        (Some(reserve_token_account), true) => cover_from_reserve(
+            &ctx.accounts.vault,
+            reserve_token_account,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.underlying_mint,
+            &ctx.accounts.token_program,
+            loss_amount,
+        )?,
+        _ => 0,
+    };
+    
+    let vault = &mut ctx.accounts.vault;
+    
+    let assets_before = vault.total_assets;
+    
+    // Reserve tokens moved into the vault replace the lost assets
+    let uncovered_loss = loss_amount
+        .checked_sub(reserve_covered)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Unvested profit acts as the next buffer
+    let buffer_absorbed = if draw_buffer {
+        absorb_loss_with_locked_profit(vault, uncovered_loss, clock.unix_timestamp)?
+    } else {
+        0
+    };
+    
+    let socialized_loss = uncovered_loss
+        .checked_sub(buffer_absorbed)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    vault.total_assets = vault.total_assets
+        .checked_sub(uncovered_loss)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // Pause deposits if the loss hitting share price is above threshold
+    let loss_bps = socialized_loss
+        .checked_mul(10000)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(assets_before.max(1))
+        .ok_or(VaultError::MathOverflow)?;
+    
+    if loss_bps > vault.loss_pause_threshold_bps as u64 {
+        vault.deposits_paused = true;
+    }
+    
+    emit!(LossReportedEvent {
+        vault: vault.key(),
+        reporter,
+        loss_amount,
+        reserve_covered,
+        buffer_absorbed,
+        socialized_loss,
+        new_total_assets: vault.total_assets,
+        deposits_paused: vault.deposits_paused,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reported loss of {} ({} covered by reserve, {} absorbed by buffer, {} socialized)", 
+         loss_amount, reserve_covered, buffer_absorbed, socialized_loss);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReportLoss<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.mint == vault.underlying_mint,
+        constraint = vault_token_account.owner == vault.key()
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Insurance reserve, drawn first when `draw_buffer` is set
+    #[account(
+        mut,
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    pub reporter: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
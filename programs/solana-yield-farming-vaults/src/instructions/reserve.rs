@@ -0,0 +1,354 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token::accessor;
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::utils::{assets_to_underlying, underlying_to_assets};
+use crate::events::{
+    ReserveBalanceEvent, ReserveFundedEvent, ReserveWithdrawalRequestedEvent, ReserveWithdrawnEvent,
+};
+
+/// Delay between requesting and executing a reserve withdrawal (48 hours)
+pub const RESERVE_WITHDRAWAL_TIMELOCK: i64 = 172800;
+
+/// Create the vault's insurance reserve token account and set the harvest cut
+pub fn initialize_reserve(
+    ctx: Context<InitializeReserve>,
+    reserve_fee_bps: u16,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can create the reserve
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    // Max 20% of each harvest
+    require!(reserve_fee_bps <= 2000, VaultError::InvalidFeeConfig);
+    
+    vault.reserve_token_account = ctx.accounts.reserve_token_account.key();
+    vault.reserve_fee_bps = reserve_fee_bps;
+    vault.pending_reserve_withdrawal = 0;
+    vault.reserve_withdrawal_unlock_ts = 0;
+    
+    msg!("Reserve initialized for vault {} with {} bps harvest cut", 
+         vault.vault_id, reserve_fee_bps);
+    
+    Ok(())
+}
+
+/// Top up the reserve from any token account of the underlying mint
+pub fn top_up_reserve(
+    ctx: Context<TopUpReserve>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(amount > 0, VaultError::InsufficientFunds);
+    
+    let balance_before = ctx.accounts.reserve_token_account.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.funder_token_account.to_account_info(),
+        mint: ctx.accounts.underlying_mint.to_account_info(),
+        to: ctx.accounts.reserve_token_account.to_account_info(),
+        authority: ctx.accounts.funder.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.underlying_mint.decimals)?;
+    
+    // Credit only what arrived (Token-2022 transfer fees are withheld from the recipient)
+    ctx.accounts.reserve_token_account.reload()?;
+    let amount_received = ctx.accounts.reserve_token_account.amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    emit!(ReserveFundedEvent {
+        vault: ctx.accounts.vault.key(),
+        funder: ctx.accounts.funder.key(),
+        amount: amount_received,
+        reserve_balance: ctx.accounts.reserve_token_account.amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Topped up reserve with {} tokens", amount_received);
+    
+    Ok(())
+}
+
+/// Queue a reserve withdrawal; it can be executed after the timelock expires
+pub fn request_reserve_withdrawal(
+    ctx: Context<RequestReserveWithdrawal>,
+    amount: u64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let clock = Clock::get()?;
+    
+    // Only vault authority can withdraw from the reserve
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    require!(
+        amount > 0 && amount <= ctx.accounts.reserve_token_account.amount,
+        VaultError::InsufficientFunds
+    );
+    
+    vault.pending_reserve_withdrawal = amount;
+    vault.reserve_withdrawal_unlock_ts = clock.unix_timestamp
+        .checked_add(RESERVE_WITHDRAWAL_TIMELOCK)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    emit!(ReserveWithdrawalRequestedEvent {
+        vault: vault.key(),
+        amount,
+        unlock_timestamp: vault.reserve_withdrawal_unlock_ts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reserve withdrawal of {} queued until {}", 
+         amount, vault.reserve_withdrawal_unlock_ts);
+    
+    Ok(())
+}
+
+/// Execute a queued reserve withdrawal after the timelock
+pub fn withdraw_reserve(ctx: Context<WithdrawReserve>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let vault = &mut ctx.accounts.vault;
+    
+    // Only vault authority can withdraw from the reserve
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    let amount = vault.pending_reserve_withdrawal;
+    require!(amount > 0, VaultError::InsufficientFunds);
+    require!(
+        clock.unix_timestamp >= vault.reserve_withdrawal_unlock_ts,
+        VaultError::ReserveTimelockActive
+    );
+    
+    // Reserve may have been drawn down since the request
+    let amount = amount.min(ctx.accounts.reserve_token_account.amount);
+    
+    let seeds = &[
+        b"vault".as_ref(),
+        &vault.vault_id.to_le_bytes(),
+        &[vault.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.reserve_token_account.to_account_info(),
+        mint: ctx.accounts.underlying_mint.to_account_info(),
+        to: ctx.accounts.destination_token_account.to_account_info(),
+        authority: vault_info,
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.underlying_mint.decimals)?;
+    
+    vault.pending_reserve_withdrawal = 0;
+    vault.reserve_withdrawal_unlock_ts = 0;
+    
+    emit!(ReserveWithdrawnEvent {
+        vault: vault.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Withdrew {} tokens from reserve", amount);
+    
+    Ok(())
+}
+
+/// Emit the current reserve balance (for off-chain monitoring)
+pub fn report_reserve_balance(ctx: Context<ReportReserveBalance>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+    
+    emit!(ReserveBalanceEvent {
+        vault: vault.key(),
+        reserve_balance: ctx.accounts.reserve_token_account.amount,
+        total_assets: vault.total_assets,
+        pending_withdrawal: vault.pending_reserve_withdrawal,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reserve balance: {}", ctx.accounts.reserve_token_account.amount);
+    
+    Ok(())
+}
+
+/// Move up to `amount` from the reserve into the vault token account to cover a
+/// loss or shortfall before it reaches share price. Returns the amount covered.
+/// Both are in vault accounting units (SOL lamports for liquid staking vaults).
+pub fn cover_from_reserve<'info>(
+    vault: &Account<'info, Vault>,
+    reserve_token_account: &InterfaceAccount<'info, TokenAccount>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    underlying_mint: &InterfaceAccount<'info, Mint>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<u64> {
+    let covered = assets_to_underlying(vault, amount)?.min(reserve_token_account.amount);
+    if covered == 0 {
+        return Ok(0);
+    }
+    
+    let seeds = &[
+        b"vault".as_ref(),
+        &vault.vault_id.to_le_bytes(),
+        &[vault.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let vault_token_info = vault_token_account.to_account_info();
+    let balance_before = accessor::amount(&vault_token_info)?;
+    
+    let cpi_accounts = TransferChecked {
+        from: reserve_token_account.to_account_info(),
+        mint: underlying_mint.to_account_info(),
+        to: vault_token_info.clone(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, covered, underlying_mint.decimals)?;
+    
+    // Only what arrived covers the loss (Token-2022 transfer fees are withheld)
+    let received = accessor::amount(&vault_token_info)?
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    underlying_to_assets(vault, received)
+}
+
+#[derive(Accounts)]
+pub struct InitializeReserve<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reserve", vault.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = vault
+    )]
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpReserve<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = funder_token_account.mint == vault.underlying_mint,
+        constraint = funder_token_account.owner == funder.key()
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub funder: Signer<'info>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RequestReserveWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawReserve<'info> {
+    #[account(
+        mut,
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == vault.underlying_mint
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReportReserveBalance<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        constraint = reserve_token_account.key() == vault.reserve_token_account @ VaultError::ReserveNotInitialized
+    )]
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+}
@@ -0,0 +1,632 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Account as SplTokenAccount;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::utils::{calculate_pending_reward, calculate_reward_accrual, calculate_reward_debt};
+use crate::events::{RewardEmissionUpdatedEvent, RewardsClaimedEvent};
+
+/// Accounts the share mint's transfer hook resolves after the standard
+/// execute accounts: [reward_distributor, source user_rewards, destination user_rewards]
+pub const REWARD_HOOK_EXTRA_ACCOUNTS: usize = 3;
+
+/// Extra account metas of a vault's share mint transfer hook
+/// User reward PDAs are derived from the owner field (offset 32) of the
+/// source (index 0) and destination (index 2) token accounts
+pub fn reward_hook_extra_account_metas(vault: &Pubkey) -> Result<Vec<ExtraAccountMeta>> {
+    let user_rewards_seeds = |token_account_index: u8| {
+        vec![
+            Seed::Literal { bytes: b"user_rewards".to_vec() },
+            Seed::Literal { bytes: vault.to_bytes().to_vec() },
+            Seed::AccountData { account_index: token_account_index, data_index: 32, length: 32 },
+        ]
+    };
+    
+    Ok(vec![
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal { bytes: b"reward_distributor".to_vec() },
+                Seed::Literal { bytes: vault.to_bytes().to_vec() },
+            ],
+            false,
+            true,
+        )?,
+        ExtraAccountMeta::new_with_seeds(&user_rewards_seeds(0), false, true)?,
+        ExtraAccountMeta::new_with_seeds(&user_rewards_seeds(2), false, true)?,
+    ])
+}
+
+/// Create a vault's reward distributor (authority only)
+pub fn initialize_reward_distributor(ctx: Context<InitializeRewardDistributor>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    let distributor = &mut ctx.accounts.reward_distributor;
+    distributor.vault = vault.key();
+    distributor.total_shares = 0;
+    distributor.emissions = [RewardEmission::default(); MAX_REWARD_EMISSIONS];
+    distributor.bump = ctx.bumps.reward_distributor;
+    
+    msg!("Reward distributor initialized for vault {}", vault.vault_id);
+    
+    Ok(())
+}
+
+/// Register a reward mint in a free emission slot and create its reward vault
+/// (authority only). Emissions start once the slot is funded
+pub fn add_reward_mint(ctx: Context<AddRewardMint>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let distributor = &mut ctx.accounts.reward_distributor;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(reward_mint != Pubkey::default(), VaultError::InvalidRewardMint);
+    
+    let emission = distributor.emissions
+        .iter_mut()
+        .find(|emission| emission.reward_mint == Pubkey::default())
+        .ok_or(VaultError::InvalidRewardMint)?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    *emission = RewardEmission {
+        reward_mint,
+        reward_vault: ctx.accounts.reward_vault.key(),
+        emissions_per_second: 0,
+        start_ts: now,
+        end_ts: now,
+        last_update_ts: now,
+        acc_reward_per_share: 0,
+        total_claimed: 0,
+    };
+    
+    msg!("Reward mint {} added to vault {}", reward_mint, vault.vault_id);
+    
+    Ok(())
+}
+
+/// Fund a reward emission and set its schedule (authority only)
+/// Emissions not yet paid out under the previous schedule roll into the new
+/// one, which must be fully covered by them plus the tokens received
+pub fn fund_reward_emission(
+    ctx: Context<FundRewardEmission>,
+    amount: u64,
+    emissions_per_second: u64,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    
+    let start_ts = start_ts.max(clock.unix_timestamp);
+    require!(end_ts > start_ts, VaultError::InvalidEmissionSchedule);
+    
+    // Pull reward tokens, crediting only what the reward vault received
+    let balance_before = ctx.accounts.reward_vault.amount;
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.authority_reward_account.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.reward_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.reward_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+    ctx.accounts.reward_vault.reload()?;
+    let amount_received = ctx.accounts.reward_vault.amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let distributor = &mut ctx.accounts.reward_distributor;
+    update_emissions(distributor, clock.unix_timestamp)?;
+    
+    let emission = distributor.emissions
+        .iter_mut()
+        .find(|emission| emission.reward_mint == ctx.accounts.reward_mint.key())
+        .ok_or(VaultError::InvalidRewardMint)?;
+    
+    let unemitted = remaining_emissions(emission, clock.unix_timestamp)?;
+    let available = unemitted
+        .checked_add(amount_received as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    let scheduled = (emissions_per_second as u128)
+        .checked_mul((end_ts - start_ts) as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    require!(scheduled <= available, VaultError::InsufficientRewardFunding);
+    
+    emission.emissions_per_second = emissions_per_second;
+    emission.start_ts = start_ts;
+    emission.end_ts = end_ts;
+    
+    emit!(RewardEmissionUpdatedEvent {
+        vault: vault.key(),
+        reward_mint: emission.reward_mint,
+        amount_funded: amount_received,
+        emissions_per_second,
+        start_ts,
+        end_ts,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Reward emission {} funded with {}: {} per second from {} to {}",
+         emission.reward_mint, amount_received, emissions_per_second, start_ts, end_ts);
+    
+    Ok(())
+}
+
+/// Opt in to a vault's reward emissions
+/// The user's held and locked shares start earning from now on
+pub fn initialize_user_rewards(ctx: Context<InitializeUserRewards>) -> Result<()> {
+    let distributor = &mut ctx.accounts.reward_distributor;
+    update_emissions(distributor, Clock::get()?.unix_timestamp)?;
+    
+    let user_rewards = &mut ctx.accounts.user_rewards;
+    user_rewards.user = ctx.accounts.user.key();
+    user_rewards.vault = ctx.accounts.vault.key();
+    user_rewards.shares = 0;
+    user_rewards.reward_debts = [0; MAX_REWARD_EMISSIONS];
+    user_rewards.pending_rewards = [0; MAX_REWARD_EMISSIONS];
+    user_rewards.bump = ctx.bumps.user_rewards;
+    
+    let locked_shares = ctx.accounts.user_lock
+        .as_ref()
+        .map_or(0, |user_lock| user_lock.shares);
+    let shares = ctx.accounts.user_share_account.amount
+        .checked_add(locked_shares)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    change_reward_shares(distributor, user_rewards, shares, 0)
+}
+
+/// Claim a user's accrued rewards of one reward mint
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &ctx.accounts.vault;
+    let distributor = &mut ctx.accounts.reward_distributor;
+    let user_rewards = &mut ctx.accounts.user_rewards;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    
+    update_emissions(distributor, clock.unix_timestamp)?;
+    change_reward_shares(distributor, user_rewards, 0, 0)?;
+    
+    let index = distributor.emissions
+        .iter()
+        .position(|emission| emission.reward_mint == reward_mint)
+        .ok_or(VaultError::InvalidRewardMint)?;
+    let amount = user_rewards.pending_rewards[index];
+    require!(amount > 0, VaultError::NoRewardsToClaim);
+    
+    user_rewards.pending_rewards[index] = 0;
+    distributor.emissions[index].total_claimed = distributor.emissions[index].total_claimed
+        .checked_add(amount)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        mint: ctx.accounts.reward_mint.to_account_info(),
+        to: ctx.accounts.user_reward_account.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.reward_token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, ctx.accounts.reward_mint.decimals)?;
+    
+    emit!(RewardsClaimedEvent {
+        vault: vault.key(),
+        user: ctx.accounts.user.key(),
+        reward_mint,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Claimed {} of reward mint {}", amount, reward_mint);
+    
+    Ok(())
+}
+
+/// Share mint transfer hook: move reward shares from the sender to the
+/// recipient. Holders who have not opted in are skipped
+pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+    // Only Token-2022 may invoke the hook, while it is transferring
+    let source_info = ctx.accounts.source_token.to_account_info();
+    let source_data = source_info.try_borrow_data()?;
+    let source_state = StateWithExtensions::<SplTokenAccount>::unpack(&source_data)?;
+    let hook_state = source_state.get_extension::<TransferHookAccount>()?;
+    require!(bool::from(hook_state.transferring), VaultError::NotTransferring);
+    
+    if ctx.accounts.source_token.owner == ctx.accounts.destination_token.owner {
+        return Ok(());
+    }
+    
+    let source_user_rewards = ctx.accounts.source_user_rewards.to_account_info();
+    let destination_user_rewards = ctx.accounts.destination_user_rewards.to_account_info();
+    require_rewards_owner(&source_user_rewards, &ctx.accounts.source_token.owner)?;
+    require_rewards_owner(&destination_user_rewards, &ctx.accounts.destination_token.owner)?;
+    
+    let distributor = ctx.accounts.reward_distributor.to_account_info();
+    checkpoint_rewards(&distributor, &source_user_rewards, 0, amount)?;
+    checkpoint_rewards(&distributor, &destination_user_rewards, amount, 0)
+}
+
+/// Reject an opted-in user rewards account that belongs to someone other
+/// than the token account owner whose shares it is about to track
+fn require_rewards_owner(user_rewards: &AccountInfo, token_owner: &Pubkey) -> Result<()> {
+    if user_rewards.owner != &crate::ID {
+        return Ok(());
+    }
+    
+    let user = UserRewards::try_deserialize(&mut &user_rewards.try_borrow_data()?[..])?;
+    require!(user.user == *token_owner, VaultError::InvalidRewardAccounts);
+    
+    Ok(())
+}
+
+/// Checkpoint a user's emission rewards around a change of their share
+/// balance. Both accounts are PDAs that may not exist (vault without
+/// emissions, user not opted in), in which case nothing is recorded
+pub fn checkpoint_rewards(
+    reward_distributor: &AccountInfo,
+    user_rewards: &AccountInfo,
+    shares_added: u64,
+    shares_removed: u64,
+) -> Result<()> {
+    if reward_distributor.owner != &crate::ID || user_rewards.owner != &crate::ID {
+        return Ok(());
+    }
+    
+    let mut distributor =
+        RewardDistributor::try_deserialize(&mut &reward_distributor.try_borrow_data()?[..])?;
+    let mut user = UserRewards::try_deserialize(&mut &user_rewards.try_borrow_data()?[..])?;
+    require!(user.vault == distributor.vault, VaultError::InvalidRewardMint);
+    
+    update_emissions(&mut distributor, Clock::get()?.unix_timestamp)?;
+    change_reward_shares(&mut distributor, &mut user, shares_added, shares_removed)?;
+    
+    distributor.try_serialize(&mut &mut reward_distributor.try_borrow_mut_data()?[..])?;
+    user.try_serialize(&mut &mut user_rewards.try_borrow_mut_data()?[..])
+}
+
+/// Accrue every emission's rewards per share up to `timestamp`
+/// While no shares are opted in the schedule is paused: its end is pushed out by
+/// the idle time, so those emissions are paid out later instead of stranded
+pub fn update_emissions(
+    distributor: &mut RewardDistributor,
+    timestamp: i64,
+) -> Result<()> {
+    let total_shares = distributor.total_shares;
+    
+    for emission in distributor.emissions.iter_mut() {
+        if emission.reward_mint == Pubkey::default() || timestamp <= emission.last_update_ts {
+            continue;
+        }
+    
+        let from = emission.last_update_ts.max(emission.start_ts);
+        let to = timestamp.min(emission.end_ts);
+        if total_shares == 0 {
+            if to > from {
+                emission.end_ts = emission.end_ts
+                    .checked_add(to - from)
+                    .ok_or(VaultError::MathOverflow)?;
+            }
+            emission.last_update_ts = timestamp;
+            continue;
+        }
+    
+        let accrual = calculate_reward_accrual(
+            emission.emissions_per_second,
+            to - from,
+            total_shares,
+        )?;
+        emission.acc_reward_per_share = emission.acc_reward_per_share
+            .checked_add(accrual)
+            .ok_or(VaultError::MathOverflow)?;
+        emission.last_update_ts = timestamp;
+    }
+    
+    Ok(())
+}
+
+/// Settle a user's pending rewards for every emission, then change their
+/// reward shares and reset their debts at the current accumulators
+pub fn change_reward_shares(
+    distributor: &mut RewardDistributor,
+    user_rewards: &mut UserRewards,
+    shares_added: u64,
+    shares_removed: u64,
+) -> Result<()> {
+    for (index, emission) in distributor.emissions.iter().enumerate() {
+        let pending = calculate_pending_reward(
+            user_rewards.shares,
+            emission.acc_reward_per_share,
+            user_rewards.reward_debts[index],
+        )?;
+        user_rewards.pending_rewards[index] = user_rewards.pending_rewards[index]
+            .checked_add(pending)
+            .ok_or(VaultError::MathOverflow)?;
+    }
+    
+    // Shares burned outside the vault were never removed; never go below zero
+    let shares_removed = shares_removed.min(user_rewards.shares);
+    user_rewards.shares = user_rewards.shares
+        .checked_sub(shares_removed)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(shares_added)
+        .ok_or(VaultError::MathOverflow)?;
+    distributor.total_shares = distributor.total_shares
+        .checked_sub(shares_removed)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(shares_added)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    for (index, emission) in distributor.emissions.iter().enumerate() {
+        user_rewards.reward_debts[index] = calculate_reward_debt(
+            user_rewards.shares,
+            emission.acc_reward_per_share,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Reward tokens an emission has yet to pay out after `timestamp`
+fn remaining_emissions(emission: &RewardEmission, timestamp: i64) -> Result<u128> {
+    let from = emission.start_ts.max(timestamp);
+    if emission.end_ts <= from {
+        return Ok(0);
+    }
+    
+    (emission.emissions_per_second as u128)
+        .checked_mul((emission.end_ts - from) as u128)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardDistributor<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = RewardDistributor::LEN,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: Account<'info, RewardDistributor>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddRewardMint<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump = reward_distributor.bump
+    )]
+    pub reward_distributor: Account<'info, RewardDistributor>,
+    
+    #[account(
+        constraint = reward_mint.key() != vault.share_mint @ VaultError::InvalidRewardMint,
+        mint::token_program = reward_token_program
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Vault-owned token account holding this mint's funded rewards
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"reward_vault", vault.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = vault,
+        token::token_program = reward_token_program
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Token program of the reward mint (SPL Token or Token-2022)
+    pub reward_token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardEmission<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump = reward_distributor.bump
+    )]
+    pub reward_distributor: Account<'info, RewardDistributor>,
+    
+    #[account(mint::token_program = reward_token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_vault", vault.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = authority_reward_account.mint == reward_mint.key(),
+        constraint = authority_reward_account.owner == authority.key()
+    )]
+    pub authority_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    /// Token program of the reward mint (SPL Token or Token-2022)
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeUserRewards<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump = reward_distributor.bump
+    )]
+    pub reward_distributor: Account<'info, RewardDistributor>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = UserRewards::LEN,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: Account<'info, UserRewards>,
+    
+    /// User's share ATA; the hook tracks shares from here on, so opting in
+    /// counts the canonical account rather than any account the user picks
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &user.key(),
+            &vault.share_mint,
+            &anchor_spl::token_2022::ID,
+        ) @ VaultError::InvalidRewardAccounts,
+        constraint = user_share_account.mint == vault.share_mint,
+        constraint = user_share_account.owner == user.key()
+    )]
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// User's share lock, if any; locked shares earn emissions too
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump = user_lock.bump
+    )]
+    pub user_lock: Option<Account<'info, UserLock>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump = reward_distributor.bump
+    )]
+    pub reward_distributor: Account<'info, RewardDistributor>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump = user_rewards.bump
+    )]
+    pub user_rewards: Account<'info, UserRewards>,
+    
+    #[account(mint::token_program = reward_token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"reward_vault", vault.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == reward_mint.key()
+    )]
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+    
+    /// Token program of the reward mint (SPL Token or Token-2022)
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Accounts passed by Token-2022 to the share mint's transfer hook
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = share_mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(token::mint = share_mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Source token account owner or delegate, checked by Token-2022
+    pub owner: UncheckedAccount<'info>,
+    
+    /// CHECK: Share mint's extra account metas, validated by seeds
+    #[account(
+        seeds = [b"extra-account-metas", share_mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault reward distributor PDA resolved from the extra account metas, may be uninitialized
+    #[account(mut)]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: Sender's user rewards PDA resolved from the extra account metas, may be uninitialized
+    #[account(mut)]
+    pub source_user_rewards: UncheckedAccount<'info>,
+    
+    /// CHECK: Recipient's user rewards PDA resolved from the extra account metas, may be uninitialized
+    #[account(mut)]
+    pub destination_user_rewards: UncheckedAccount<'info>,
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022_extensions::{self, TokenMetadataUpdateField};
+use anchor_spl::token_interface::Mint;
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+use crate::state::*;
+use crate::errors::VaultError;
+use crate::events::ShareMetadataUpdated;
+
+/// Maximum share token name length (e.g. "YV-USDC-AutoCompound")
+pub const MAX_SHARE_NAME_LEN: usize = 32;
+/// Maximum share token symbol length (e.g. "yvUSDC")
+pub const MAX_SHARE_SYMBOL_LEN: usize = 10;
+/// Maximum share token metadata URI length
+pub const MAX_SHARE_URI_LEN: usize = 200;
+
+/// Validate share token metadata field lengths (`None` fields are skipped)
+pub fn validate_share_metadata(
+    name: Option<&str>,
+    symbol: Option<&str>,
+    uri: Option<&str>,
+) -> Result<()> {
+    if let Some(name) = name {
+        require!(
+            !name.is_empty() && name.len() <= MAX_SHARE_NAME_LEN,
+            VaultError::InvalidShareMetadata
+        );
+    }
+    if let Some(symbol) = symbol {
+        require!(
+            !symbol.is_empty() && symbol.len() <= MAX_SHARE_SYMBOL_LEN,
+            VaultError::InvalidShareMetadata
+        );
+    }
+    if let Some(uri) = uri {
+        require!(uri.len() <= MAX_SHARE_URI_LEN, VaultError::InvalidShareMetadata);
+    }
+    Ok(())
+}
+
+/// Read the metadata currently stored in the share mint, if any
+pub fn read_share_metadata(share_mint: &AccountInfo) -> Result<Option<TokenMetadata>> {
+    let data = share_mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(state.get_variable_len_extension::<TokenMetadata>().ok())
+}
+
+/// Top up the share mint to rent exemption for `metadata` before it is written
+/// Token-2022 reallocates the mint for the metadata TLV entry but does not
+/// move lamports and rejects the resize if the mint would fall below rent
+/// exemption, so the payer covers the difference ahead of the CPI
+pub fn fund_share_mint_rent<'info>(
+    share_mint: &AccountInfo<'info>,
+    metadata: &TokenMetadata,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let current_len = match read_share_metadata(share_mint)? {
+        Some(current) => current.tlv_size_of()?,
+        None => 0,
+    };
+    let data_len = share_mint.data_len()
+        .checked_sub(current_len)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(metadata.tlv_size_of()?)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    let required = Rent::get()?.minimum_balance(data_len);
+    let shortfall = required.saturating_sub(share_mint.lamports());
+    
+    if shortfall > 0 {
+        let cpi_accounts = Transfer {
+            from: payer.clone(),
+            to: share_mint.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+    
+    Ok(())
+}
+
+/// Update the share token's name, symbol and/or URI (authority only)
+/// The vault PDA is the metadata update authority and signs the update
+pub fn update_share_metadata(
+    ctx: Context<UpdateShareMetadata>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    
+    require!(
+        ctx.accounts.authority.key() == vault.authority,
+        VaultError::Unauthorized
+    );
+    require!(
+        name.is_some() || symbol.is_some() || uri.is_some(),
+        VaultError::InvalidShareMetadata
+    );
+    
+    validate_share_metadata(name.as_deref(), symbol.as_deref(), uri.as_deref())?;
+    
+    // Fund the resized mint before Token-2022 grows it
+    let mut metadata = read_share_metadata(&ctx.accounts.share_mint.to_account_info())?
+        .ok_or(VaultError::InvalidShareMetadata)?;
+    for (field, value) in [
+        (Field::Name, &name),
+        (Field::Symbol, &symbol),
+        (Field::Uri, &uri),
+    ] {
+        if let Some(value) = value {
+            metadata.update(field, value.clone());
+        }
+    }
+    fund_share_mint_rent(
+        &ctx.accounts.share_mint.to_account_info(),
+        &metadata,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
+    let signer = &[&seeds[..]];
+    
+    for (field, value) in [
+        (Field::Name, &name),
+        (Field::Symbol, &symbol),
+        (Field::Uri, &uri),
+    ] {
+        let Some(value) = value else {
+            continue;
+        };
+        let cpi_accounts = TokenMetadataUpdateField {
+            token_program_id: ctx.accounts.token_program.to_account_info(),
+            metadata: ctx.accounts.share_mint.to_account_info(),
+            update_authority: vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_2022_extensions::token_metadata_update_field(cpi_ctx, field, value.clone())?;
+    }
+    
+    emit!(ShareMetadataUpdated {
+        vault: vault.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        name,
+        symbol,
+        uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Updated share metadata for vault {}", vault.vault_id);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateShareMetadata<'info> {
+    #[account(
+        seeds = [b"vault", vault.vault_id.to_le_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+    
+    #[account(
+        mut,
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = token_program
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+    
+    /// Vault authority, pays for any metadata growth
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -1,31 +1,87 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
 use crate::errors::VaultError;
-use crate::utils::calculate_assets_from_shares;
+use crate::utils::{
+    assets_to_underlying, calculate_assets_from_shares, calculate_locked_profit,
+};
 use crate::events::WithdrawEvent;
+use super::position::{held_shares, track_withdrawal};
+use super::rewards::checkpoint_rewards;
 
 /// Withdraw assets from a vault by burning shares
 pub fn withdraw(
     ctx: Context<Withdraw>,
     shares: u64,
 ) -> Result<()> {
-    let vault = &mut ctx.accounts.vault;
+    let destination = ctx.accounts.user_token_account.to_account_info();
+    
+    withdraw_from_vault(
+        WithdrawAccounts {
+            vault: &mut ctx.accounts.vault,
+            vault_token_account: &ctx.accounts.vault_token_account,
+            underlying_mint: &ctx.accounts.underlying_mint,
+            token_program: ctx.accounts.token_program.to_account_info(),
+            share_mint: &ctx.accounts.share_mint,
+            user_share_account: &ctx.accounts.user_share_account,
+            share_token_program: &ctx.accounts.share_token_program,
+            user: &ctx.accounts.user,
+            user_position: &ctx.accounts.user_position,
+            user_lock: &ctx.accounts.user_lock,
+            reward_distributor: &ctx.accounts.reward_distributor,
+            user_rewards: &ctx.accounts.user_rewards,
+        },
+        destination,
+        shares,
+    )
+}
+
+/// Accounts a withdrawal burns shares and pays out underlying tokens through
+/// (shared by `withdraw` and `withdraw_sol`)
+pub(crate) struct WithdrawAccounts<'a, 'info> {
+    pub vault: &'a mut Account<'info, Vault>,
+    pub vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub underlying_mint: &'a InterfaceAccount<'info, Mint>,
+    pub token_program: AccountInfo<'info>,
+    pub share_mint: &'a InterfaceAccount<'info, Mint>,
+    pub user_share_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub share_token_program: &'a Interface<'info, TokenInterface>,
+    pub user: &'a Signer<'info>,
+    pub user_position: &'a UncheckedAccount<'info>,
+    pub user_lock: &'a UncheckedAccount<'info>,
+    pub reward_distributor: &'a UncheckedAccount<'info>,
+    pub user_rewards: &'a UncheckedAccount<'info>,
+}
+
+/// Burn `shares`, pay the underlying tokens they are worth into `destination`
+/// and update vault accounting and the withdrawer's position
+pub(crate) fn withdraw_from_vault<'info>(
+    accounts: WithdrawAccounts<'_, 'info>,
+    destination: AccountInfo<'info>,
+    shares: u64,
+) -> Result<()> {
+    let vault = accounts.vault;
     let clock = Clock::get()?;
     
     // Check vault is not paused
     require!(!vault.paused, VaultError::VaultPaused);
+    require!(
+        vault.token_b_mint == Pubkey::default(),
+        VaultError::DualAssetVault
+    );
     
     // Check user has enough shares
     require!(
-        ctx.accounts.user_share_account.amount >= shares,
+        accounts.user_share_account.amount >= shares,
         VaultError::InsufficientFunds
     );
     
-    // Calculate assets to withdraw
+    // Calculate assets to withdraw (locked profit stays in the vault until it vests)
+    let locked_profit = calculate_locked_profit(vault, clock.unix_timestamp)?;
     let assets_to_withdraw = calculate_assets_from_shares(
         shares,
         vault.total_assets,
+        locked_profit,
         vault.total_shares,
     )?;
     
@@ -35,38 +91,46 @@ pub fn withdraw(
         VaultError::InsufficientFunds
     );
     
+    // Liquid staking vaults pay out the LST worth the withdrawn SOL value
+    let tokens_to_withdraw = assets_to_underlying(vault, assets_to_withdraw)?;
+    
     // Check vault token account has enough balance
     require!(
-        assets_to_withdraw <= ctx.accounts.vault_token_account.amount,
+        tokens_to_withdraw <= accounts.vault_token_account.amount,
         VaultError::InsufficientFunds
     );
     
     // Burn user's shares
     let cpi_accounts = Burn {
-        mint: ctx.accounts.share_mint.to_account_info(),
-        from: ctx.accounts.user_share_account.to_account_info(),
-        authority: ctx.accounts.user.to_account_info(),
+        mint: accounts.share_mint.to_account_info(),
+        from: accounts.user_share_account.to_account_info(),
+        authority: accounts.user.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = accounts.share_token_program.to_account_info();
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-    token::burn(cpi_ctx, shares)?;
+    token_interface::burn(cpi_ctx, shares)?;
+    
+    // Burned shares stop earning emission rewards
+    checkpoint_rewards(
+        accounts.reward_distributor,
+        accounts.user_rewards,
+        0,
+        shares,
+    )?;
     
     // Transfer assets from vault to user
-    let seeds = &[
-        b"vault",
-        &vault.vault_id.to_le_bytes(),
-        &[vault.bump],
-    ];
+    let vault_id_bytes = vault.vault_id.to_le_bytes();
+    let seeds = &[b"vault".as_ref(), vault_id_bytes.as_ref(), &[vault.bump]];
     let signer = &[&seeds[..]];
     
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.vault_token_account.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.vault.to_account_info(),
+    let cpi_accounts = TransferChecked {
+        from: accounts.vault_token_account.to_account_info(),
+        mint: accounts.underlying_mint.to_account_info(),
+        to: destination,
+        authority: vault.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    token::transfer(cpi_ctx, assets_to_withdraw)?;
+    let cpi_ctx = CpiContext::new_with_signer(accounts.token_program, cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, tokens_to_withdraw, accounts.underlying_mint.decimals)?;
     
     // Update vault state
     vault.total_assets = vault.total_assets
@@ -76,17 +140,25 @@ pub fn withdraw(
         .checked_sub(shares)
         .ok_or(VaultError::MathOverflow)?;
     
+    track_withdrawal(
+        &accounts.user_position.to_account_info(),
+        held_shares(accounts.user_share_account, &accounts.user_lock.to_account_info())?,
+        assets_to_withdraw,
+        shares,
+        clock.unix_timestamp,
+    )?;
+    
     emit!(WithdrawEvent {
         vault: vault.key(),
-        user: ctx.accounts.user.key(),
-        amount: assets_to_withdraw,
+        user: accounts.user.key(),
+        amount: tokens_to_withdraw,
         shares_burned: shares,
         total_assets: vault.total_assets,
         total_shares: vault.total_shares,
         timestamp: clock.unix_timestamp,
     });
     
-    msg!("Withdrew {} tokens by burning {} shares", assets_to_withdraw, shares);
+    msg!("Withdrew {} tokens by burning {} shares", tokens_to_withdraw, shares);
     
     Ok(())
 }
@@ -105,30 +177,74 @@ pub struct Withdraw<'info> {
         constraint = vault_token_account.mint == vault.underlying_mint,
         constraint = vault_token_account.owner == vault.key()
     )]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         constraint = user_token_account.mint == vault.underlying_mint,
         constraint = user_token_account.owner == user.key()
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = underlying_mint.key() == vault.underlying_mint,
+        mint::token_program = token_program
+    )]
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
-        constraint = share_mint.key() == vault.share_mint
+        constraint = share_mint.key() == vault.share_mint,
+        mint::token_program = share_token_program
     )]
-    pub share_mint: Account<'info, Mint>,
+    pub share_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         mut,
         constraint = user_share_account.mint == vault.share_mint,
         constraint = user_share_account.owner == user.key()
     )]
-    pub user_share_account: Account<'info, TokenAccount>,
+    pub user_share_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(mut)]
     pub user: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    /// Token program of the underlying mint (SPL Token or Token-2022)
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    /// Token program of the share mint (SPL Token or Token-2022)
+    pub share_token_program: Interface<'info, TokenInterface>,
+    
+    /// CHECK: Withdrawer's position PDA, uninitialized until the user opens a position;
+    /// once opened its cost basis and PnL are always updated
+    #[account(
+        mut,
+        seeds = [b"user_position", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: UncheckedAccount<'info>,
+    
+    /// CHECK: User's share lock PDA, uninitialized if the user has no lock;
+    /// locked shares still carry the position's cost basis
+    #[account(
+        seeds = [b"user_lock", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lock: UncheckedAccount<'info>,
+    
+    /// CHECK: Vault reward distributor PDA, uninitialized if the vault has no emissions
+    #[account(
+        mut,
+        seeds = [b"reward_distributor", vault.key().as_ref()],
+        bump
+    )]
+    pub reward_distributor: UncheckedAccount<'info>,
+    
+    /// CHECK: User's emission rewards PDA, uninitialized if the user has not opted in
+    #[account(
+        mut,
+        seeds = [b"user_rewards", vault.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_rewards: UncheckedAccount<'info>,
 }
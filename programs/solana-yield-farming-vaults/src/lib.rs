@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+pub mod adapters;
 pub mod errors;
 pub mod events;
 pub mod state;
@@ -7,8 +8,9 @@ pub mod utils;
 pub mod instructions;
 
 use instructions::*;
+use state::*;
 
-declare_id!("YvLtV8X9JzKpQmN3RfH5W2B6C4D7E8F9G0");
+declare_id!("BKag4ciZ7ako8995U3kSCyFeoz5bhJ5D9LfwTZj9airE");
 
 #[program]
 pub mod solana_yield_farming_vaults {
@@ -24,6 +26,7 @@ pub mod solana_yield_farming_vaults {
     }
 
     /// Initialize a new vault
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_vault(
         ctx: Context<InitializeVault>,
         strategy: u8,
@@ -31,6 +34,9 @@ pub mod solana_yield_farming_vaults {
         performance_fee_bps: Option<u16>,
         max_leverage_bps: Option<u16>,
         min_deposit: u64,
+        share_name: String,
+        share_symbol: String,
+        share_uri: String,
     ) -> Result<()> {
         instructions::initialize::initialize_vault(
             ctx,
@@ -39,6 +45,9 @@ pub mod solana_yield_farming_vaults {
             performance_fee_bps,
             max_leverage_bps,
             min_deposit,
+            share_name,
+            share_symbol,
+            share_uri,
         )
     }
 
@@ -58,14 +67,47 @@ pub mod solana_yield_farming_vaults {
         instructions::withdraw::withdraw(ctx, shares)
     }
 
+    /// Add a second token (token B) to an empty vault, making it dual-asset
+    pub fn initialize_token_b(
+        ctx: Context<InitializeTokenB>,
+    ) -> Result<()> {
+        instructions::pair::initialize_token_b(ctx)
+    }
+
+    /// Deposit both tokens into a dual-asset vault
+    pub fn deposit_pair(
+        ctx: Context<DepositPair>,
+        amount_a: u64,
+        amount_b: u64,
+        swap_to_ratio: bool,
+    ) -> Result<()> {
+        instructions::pair::deposit_pair(ctx, amount_a, amount_b, swap_to_ratio)
+    }
+
+    /// Withdraw both tokens from a dual-asset vault
+    pub fn withdraw_pair(
+        ctx: Context<WithdrawPair>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::pair::withdraw_pair(ctx, shares)
+    }
+
     /// Harvest rewards and auto-compound
-    pub fn harvest(
-        ctx: Context<Harvest>,
+    /// Reward tokens in other mints are swapped into the underlying via remaining accounts
+    pub fn harvest<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Harvest<'info>>,
         rewards_amount: u64,
     ) -> Result<()> {
         instructions::harvest::harvest(ctx, rewards_amount)
     }
 
+    /// Create the vault's harvest rewards account for a reward mint (authority only)
+    pub fn initialize_harvest_rewards_account(
+        ctx: Context<InitializeHarvestRewardsAccount>,
+    ) -> Result<()> {
+        instructions::harvest::initialize_harvest_rewards_account(ctx)
+    }
+
     /// Collect accrued fees to treasury
     pub fn collect_fees(
         ctx: Context<CollectFees>,
@@ -92,39 +134,269 @@ pub mod solana_yield_farming_vaults {
     /// Update vault parameters
     pub fn update_vault_params(
         ctx: Context<UpdateVaultParams>,
-        management_fee_bps: Option<u16>,
-        performance_fee_bps: Option<u16>,
-        harvest_cooldown: Option<i64>,
-        rebalance_cooldown: Option<i64>,
-        rebalance_threshold_bps: Option<u16>,
-        min_deposit: Option<u64>,
+        params: VaultParamsUpdate,
     ) -> Result<()> {
-        instructions::rebalance::update_vault_params(
-            ctx,
-            management_fee_bps,
-            performance_fee_bps,
-            harvest_cooldown,
-            rebalance_cooldown,
-            rebalance_threshold_bps,
-            min_deposit,
-        )
+        instructions::rebalance::update_vault_params(ctx, params)
+    }
+
+    /// Create a user position (opens a lending obligation for leveraged strategies)
+    pub fn initialize_user_position(
+        ctx: Context<InitializeUserPosition>,
+    ) -> Result<()> {
+        instructions::position::initialize_user_position(ctx)
+    }
+
+    /// View a user position's current value and realized/unrealized PnL
+    pub fn get_position_pnl(ctx: Context<GetPositionPnl>) -> Result<PositionPnl> {
+        instructions::position::get_position_pnl(ctx)
     }
 
     /// Liquidate an undercollateralized position
-    pub fn liquidate(
-        ctx: Context<Liquidate>,
-        collateral_to_seize: u64,
+    pub fn liquidate<'info>(
+        ctx: Context<'_, '_, '_, 'info, Liquidate<'info>>,
+        debt_to_repay: u64,
+    ) -> Result<()> {
+        instructions::liquidate::liquidate(ctx, debt_to_repay)
+    }
+
+    /// Start a Dutch liquidation auction for an unhealthy position
+    pub fn start_liquidation_auction(
+        ctx: Context<StartLiquidationAuction>,
+    ) -> Result<()> {
+        instructions::liquidate::start_liquidation_auction(ctx)
+    }
+
+    /// Cancel a running liquidation auction once the position is healthy again
+    pub fn cancel_liquidation_auction(
+        ctx: Context<CancelLiquidationAuction>,
     ) -> Result<()> {
-        instructions::liquidate::liquidate(ctx, collateral_to_seize)
+        instructions::liquidate::cancel_liquidation_auction(ctx)
     }
 
     /// Adjust leverage for a leveraged position
-    pub fn adjust_leverage(
-        ctx: Context<AdjustLeverage>,
+    pub fn adjust_leverage<'info>(
+        ctx: Context<'_, '_, '_, 'info, AdjustLeverage<'info>>,
         target_leverage_bps: u16,
         collateral_add: u64,
+        collateral_remove: u64,
+    ) -> Result<()> {
+        instructions::liquidate::adjust_leverage(
+            ctx,
+            target_leverage_bps,
+            collateral_add,
+            collateral_remove,
+        )
+    }
+
+    /// Deleverage an at-risk position without penalty (permissionless keeper)
+    pub fn auto_deleverage<'info>(
+        ctx: Context<'_, '_, '_, 'info, AutoDeleverage<'info>>,
+    ) -> Result<()> {
+        instructions::liquidate::auto_deleverage(ctx)
+    }
+
+    /// Emit a vault-wide leverage and health summary
+    pub fn report_vault_health(
+        ctx: Context<ReportVaultHealth>,
+    ) -> Result<()> {
+        instructions::liquidate::report_vault_health(ctx)
+    }
+
+    /// Open the perp hedge position for a delta-neutral vault
+    pub fn initialize_hedge(
+        ctx: Context<InitializeHedge>,
+        margin_amount: u64,
+    ) -> Result<()> {
+        instructions::hedge::initialize_hedge(ctx, margin_amount)
+    }
+
+    /// Post additional margin to the hedge position
+    pub fn deposit_hedge_margin(
+        ctx: Context<DepositHedgeMargin>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::hedge::deposit_hedge_margin(ctx, amount)
+    }
+
+    /// Resize the hedge when net delta leaves the rehedge band (permissionless keeper)
+    pub fn rehedge(
+        ctx: Context<Rehedge>,
+    ) -> Result<()> {
+        instructions::hedge::rehedge(ctx)
+    }
+
+    /// Open the vault's CLMM position and deploy liquidity
+    pub fn open_clmm_position(
+        ctx: Context<OpenClmmPosition>,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_amount: u64,
+    ) -> Result<()> {
+        instructions::clmm::open_clmm_position(ctx, tick_lower, tick_upper, liquidity_amount)
+    }
+
+    /// Move liquidity between the vault and its CLMM position
+    pub fn adjust_clmm_liquidity(
+        ctx: Context<AdjustClmmLiquidity>,
+        amount: u64,
+        increase: bool,
+    ) -> Result<()> {
+        instructions::clmm::adjust_clmm_liquidity(ctx, amount, increase)
+    }
+
+    /// Recenter an out-of-range CLMM position on the current price (authority only)
+    pub fn reset_range(
+        ctx: Context<ResetRange>,
+    ) -> Result<()> {
+        instructions::clmm::reset_range(ctx)
+    }
+
+    /// Deposit native SOL into a wrapped-SOL vault (wraps automatically)
+    pub fn deposit_sol(
+        ctx: Context<DepositSol>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::native_sol::deposit_sol(ctx, lamports)
+    }
+
+    /// Withdraw native SOL from a wrapped-SOL vault (unwraps automatically)
+    pub fn withdraw_sol(
+        ctx: Context<WithdrawSol>,
+        shares: u64,
+    ) -> Result<()> {
+        instructions::native_sol::withdraw_sol(ctx, shares)
+    }
+
+    /// Attach the stake pool of a liquid staking vault (authority only)
+    pub fn initialize_stake_pool(ctx: Context<InitializeStakePool>) -> Result<()> {
+        instructions::liquid_staking::initialize_stake_pool(ctx)
+    }
+
+    /// Enable share locking with a harvest boost and early-unlock penalty (authority only)
+    pub fn initialize_locks(
+        ctx: Context<InitializeLocks>,
+        lock_boost_bps: u16,
+        early_unlock_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::lock::initialize_locks(ctx, lock_boost_bps, early_unlock_penalty_bps)
+    }
+
+    /// Update the lock boost and early-unlock penalty (authority only)
+    pub fn update_lock_params(
+        ctx: Context<UpdateLockParams>,
+        lock_boost_bps: u16,
+        early_unlock_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::lock::update_lock_params(ctx, lock_boost_bps, early_unlock_penalty_bps)
+    }
+
+    /// Create a user's lock account for a vault
+    pub fn initialize_user_lock(ctx: Context<InitializeUserLock>) -> Result<()> {
+        instructions::lock::initialize_user_lock(ctx)
+    }
+
+    /// Lock vault shares for a tier (0 = 30 days, 1 = 90 days, 2 = 180 days)
+    pub fn lock_shares(ctx: Context<LockShares>, shares: u64, tier: u8) -> Result<()> {
+        instructions::lock::lock_shares(ctx, shares, tier)
+    }
+
+    /// Unlock locked shares plus accrued boost (penalized before expiry)
+    pub fn unlock_shares(ctx: Context<UnlockShares>) -> Result<()> {
+        instructions::lock::unlock_shares(ctx)
+    }
+
+    /// Create a vault's reward distributor for secondary emissions (authority only)
+    pub fn initialize_reward_distributor(ctx: Context<InitializeRewardDistributor>) -> Result<()> {
+        instructions::rewards::initialize_reward_distributor(ctx)
+    }
+
+    /// Register a reward mint for emissions to share holders (authority only)
+    pub fn add_reward_mint(ctx: Context<AddRewardMint>) -> Result<()> {
+        instructions::rewards::add_reward_mint(ctx)
+    }
+
+    /// Fund a reward emission and set its schedule (authority only)
+    pub fn fund_reward_emission(
+        ctx: Context<FundRewardEmission>,
+        amount: u64,
+        emissions_per_second: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        instructions::rewards::fund_reward_emission(ctx, amount, emissions_per_second, start_ts, end_ts)
+    }
+
+    /// Opt in to a vault's reward emissions
+    pub fn initialize_user_rewards(ctx: Context<InitializeUserRewards>) -> Result<()> {
+        instructions::rewards::initialize_user_rewards(ctx)
+    }
+
+    /// Claim accrued emission rewards of one reward mint
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::rewards::claim_rewards(ctx)
+    }
+
+    /// Share mint transfer hook: checkpoint emission rewards of sender and recipient
+    #[interface(spl_transfer_hook_interface::execute)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, amount: u64) -> Result<()> {
+        instructions::rewards::transfer_hook(ctx, amount)
+    }
+
+    /// Update the vault share token's metadata (authority only)
+    pub fn update_share_metadata(
+        ctx: Context<UpdateShareMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        instructions::share_metadata::update_share_metadata(ctx, name, symbol, uri)
+    }
+
+    /// Report a strategy loss (authority or strategy adapter)
+    pub fn report_loss(
+        ctx: Context<ReportLoss>,
+        loss_amount: u64,
+        draw_buffer: bool,
+    ) -> Result<()> {
+        instructions::report_loss::report_loss(ctx, loss_amount, draw_buffer)
+    }
+
+    /// Create the vault's insurance reserve
+    pub fn initialize_reserve(
+        ctx: Context<InitializeReserve>,
+        reserve_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::reserve::initialize_reserve(ctx, reserve_fee_bps)
+    }
+
+    /// Top up the insurance reserve
+    pub fn top_up_reserve(
+        ctx: Context<TopUpReserve>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::reserve::top_up_reserve(ctx, amount)
+    }
+
+    /// Queue a timelocked reserve withdrawal (authority only)
+    pub fn request_reserve_withdrawal(
+        ctx: Context<RequestReserveWithdrawal>,
+        amount: u64,
     ) -> Result<()> {
-        instructions::liquidate::adjust_leverage(ctx, target_leverage_bps, collateral_add)
+        instructions::reserve::request_reserve_withdrawal(ctx, amount)
+    }
+
+    /// Execute a queued reserve withdrawal after the timelock
+    pub fn withdraw_reserve(
+        ctx: Context<WithdrawReserve>,
+    ) -> Result<()> {
+        instructions::reserve::withdraw_reserve(ctx)
+    }
+
+    /// Emit the current reserve balance
+    pub fn report_reserve_balance(
+        ctx: Context<ReportReserveBalance>,
+    ) -> Result<()> {
+        instructions::reserve::report_reserve_balance(ctx)
     }
 
     /// Pause a vault
@@ -140,4 +412,12 @@ pub mod solana_yield_farming_vaults {
     ) -> Result<()> {
         instructions::pause::unpause_vault(ctx)
     }
+
+    /// Migrate a vault created by the original program to the current layout
+    pub fn migrate_vault(
+        ctx: Context<MigrateVault>,
+        vault_id: u64,
+    ) -> Result<()> {
+        instructions::migrate::migrate_vault(ctx, vault_id)
+    }
 }
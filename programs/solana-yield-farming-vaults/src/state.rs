@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
 
 /// Global protocol state - stores protocol-wide configuration
 #[account]
@@ -44,7 +43,8 @@ pub struct Vault {
     /// Vault identifier (unique per vault)
     pub vault_id: u64,
     
-    /// Strategy type (0 = LP Farming, 1 = Leveraged Yield, 2 = Auto-Compound, 3 = Delta-Neutral)
+    /// Strategy type (0 = LP Farming, 1 = Leveraged Yield, 2 = Auto-Compound, 3 = Delta-Neutral,
+    /// 4 = Liquid Staking)
     pub strategy: u8,
     
     /// Underlying asset mint (e.g., SOL, USDC)
@@ -59,7 +59,7 @@ pub struct Vault {
     /// Vault authority (can pause, update strategy params)
     pub authority: Pubkey,
     
-    /// Total assets under management (in underlying token units)
+    /// Total assets under management (in underlying token units; SOL lamports for liquid staking)
     pub total_assets: u64,
     
     /// Total shares minted
@@ -95,7 +95,7 @@ pub struct Vault {
     /// Maximum leverage (for leveraged strategies, in basis points: 20000 = 2x)
     pub max_leverage_bps: u16,
     
-    /// Current leverage (basis points)
+    /// Current vault-wide leverage, derived from total_collateral and total_debt (basis points)
     pub current_leverage_bps: u16,
     
     /// Rebalance threshold (basis points: deviation from target before rebalancing)
@@ -107,6 +107,78 @@ pub struct Vault {
     /// Rebalance cooldown period (seconds)
     pub rebalance_cooldown: i64,
     
+    /// Share of realized rewards paid to the harvester (basis points)
+    pub harvest_bounty_bps: u16,
+    
+    /// Profit realized at the last harvest that has not vested into share price yet
+    pub locked_profit: u64,
+    
+    /// Time over which harvested profit unlocks linearly (seconds)
+    pub profit_unlock_duration: i64,
+    
+    /// Deposits paused flag (set automatically after a large reported loss)
+    pub deposits_paused: bool,
+    
+    /// Maximum loss that can be reported in a single call (basis points of total assets)
+    pub max_loss_report_bps: u16,
+    
+    /// Socialized loss above which deposits are paused (basis points of total assets)
+    pub loss_pause_threshold_bps: u16,
+    
+    /// Insurance reserve token account (PDA), default pubkey if no reserve
+    pub reserve_token_account: Pubkey,
+    
+    /// Share of each harvest routed to the reserve (basis points)
+    pub reserve_fee_bps: u16,
+    
+    /// Reserve withdrawal queued by the authority
+    pub pending_reserve_withdrawal: u64,
+    
+    /// Timestamp after which the queued reserve withdrawal can execute
+    pub reserve_withdrawal_unlock_ts: i64,
+    
+    /// Aggregate collateral of all leveraged positions (underlying mint)
+    /// Position collateral belongs to the position owner, so it is kept out of
+    /// total_assets and the share price
+    pub total_collateral: u64,
+    
+    /// Aggregate debt of all leveraged positions (debt mint)
+    pub total_debt: u64,
+    
+    /// For delta-neutral: signed base size of the perp hedge (negative = short)
+    pub hedge_size: i64,
+    
+    /// For delta-neutral: spot exposure plus hedge size at the last update (underlying units)
+    pub net_delta: i64,
+    
+    /// For dual-asset vaults: second token mint (default pubkey = single-asset vault)
+    pub token_b_mint: Pubkey,
+    
+    /// For dual-asset vaults: vault's token account holding token B
+    pub vault_token_b_account: Pubkey,
+    
+    /// For dual-asset vaults: token B under management (token B units)
+    pub total_assets_b: u64,
+    
+    /// For liquid staking: lamports per 10^9 LST base units realized at the last
+    /// harvest (0 = not a liquid staking vault)
+    pub lst_exchange_rate: u64,
+    
+    /// Share of harvested rewards boosted to locked shares (basis points, 0 = no locks)
+    pub lock_boost_bps: u16,
+    
+    /// Share of a lock forfeited to the other lockers when unlocked early (basis points)
+    pub early_unlock_penalty_bps: u16,
+    
+    /// Shares held in the lock escrow
+    pub total_locked_shares: u64,
+    
+    /// Sum of all locks' tier-weighted shares
+    pub total_lock_weight: u64,
+    
+    /// Boost shares accrued per unit of lock weight (scaled by BOOST_PRECISION)
+    pub acc_boost_per_weight: u128,
+    
     /// Strategy-specific configuration (strategy-dependent)
     pub strategy_config: StrategyConfig,
     
@@ -138,6 +210,29 @@ impl Vault {
         2 +  // rebalance_threshold_bps
         8 +  // harvest_cooldown
         8 +  // rebalance_cooldown
+        2 +  // harvest_bounty_bps
+        8 +  // locked_profit
+        8 +  // profit_unlock_duration
+        1 +  // deposits_paused
+        2 +  // max_loss_report_bps
+        2 +  // loss_pause_threshold_bps
+        32 + // reserve_token_account
+        2 +  // reserve_fee_bps
+        8 +  // pending_reserve_withdrawal
+        8 +  // reserve_withdrawal_unlock_ts
+        8 +  // total_collateral
+        8 +  // total_debt
+        8 +  // hedge_size
+        8 +  // net_delta
+        32 + // token_b_mint
+        32 + // vault_token_b_account
+        8 +  // total_assets_b
+        8 +  // lst_exchange_rate
+        2 +  // lock_boost_bps
+        2 +  // early_unlock_penalty_bps
+        8 +  // total_locked_shares
+        8 +  // total_lock_weight
+        16 + // acc_boost_per_weight
         StrategyConfig::LEN + // strategy_config
         1;   // bump
 }
@@ -154,7 +249,7 @@ pub struct StrategyConfig {
     /// For leveraged strategies: collateral factor (basis points)
     pub collateral_factor_bps: Option<u16>,
     
-    /// For delta-neutral: hedge position address (e.g., perp market)
+    /// For delta-neutral: vault-owned perp position holding the hedge
     pub hedge_position: Option<Pubkey>,
     
     /// Target allocation percentages (for multi-asset strategies)
@@ -169,8 +264,82 @@ pub struct StrategyConfig {
     /// Health factor threshold for liquidation (basis points, e.g., 11000 = 1.1x)
     pub liquidation_threshold_bps: Option<u16>,
     
+    /// Maximum share of a position's debt repaid per liquidation (basis points, e.g., 5000 = 50%)
+    pub close_factor_bps: Option<u16>,
+    
+    /// Collateral paid to liquidators per unit of debt repaid (basis points, e.g., 10500 = 5% bonus)
+    pub liquidation_bonus_bps: Option<u16>,
+    
+    /// Protocol share of the liquidation bonus sent to the treasury (basis points of the bonus)
+    pub liquidation_protocol_fee_bps: Option<u16>,
+    
+    /// Dutch-auction mode: maximum liquidation bonus reached at the end of the auction
+    /// (basis points, e.g., 11000 = 10% bonus; None = fixed `liquidation_bonus_bps`)
+    pub auction_max_bonus_bps: Option<u16>,
+    
+    /// Seconds for the auction bonus to grow from 0% to the maximum
+    pub auction_duration: Option<i64>,
+    
+    /// Soft health threshold below which keepers may auto-deleverage (basis points,
+    /// above liquidation_threshold_bps; None = auto-deleverage disabled)
+    pub deleverage_threshold_bps: Option<u16>,
+    
+    /// Leverage auto-deleverage reduces positions to (basis points, e.g., 15000 = 1.5x)
+    pub deleverage_target_leverage_bps: Option<u16>,
+    
+    /// Keeper fee for auto-deleverage (basis points of repaid debt)
+    pub keeper_fee_bps: Option<u16>,
+    
+    /// For reward swaps: swap adapter program used to convert rewards into the underlying mint
+    pub swap_program: Option<Pubkey>,
+    
+    /// Maximum slippage vs. oracle price allowed on reward swaps (basis points)
+    pub max_swap_slippage_bps: u16,
+    
+    /// Reward mints harvest may swap into the underlying mint (up to 4)
+    pub reward_mints: [RewardMintConfig; 4],
+    
+    /// Strategy adapter allowed to report losses alongside the vault authority
+    pub strategy_adapter: Option<Pubkey>,
+    
+    /// For leveraged strategies: market on the lending protocol positions borrow from
+    pub lending_market: Option<Pubkey>,
+    
+    /// For leveraged strategies: mint borrowed from the lending market (None = underlying mint)
+    pub debt_mint: Option<Pubkey>,
+    
+    /// Oracle price feed for the debt mint (Pyth)
+    pub debt_oracle_price_feed: Option<Pubkey>,
+    
+    /// For delta-neutral: perp adapter program the hedge is placed on
+    pub perp_program: Option<Pubkey>,
+    
+    /// For delta-neutral: perp market (margined in the underlying mint)
+    pub perp_market: Option<Pubkey>,
+    
+    /// Net delta tolerated before rehedging (basis points of total assets, e.g., 200 = 2%)
+    pub rehedge_band_bps: Option<u16>,
+    
+    /// For CLMM LP strategies: CLMM adapter program (Orca Whirlpool/Raydium CLMM-style)
+    pub clmm_program: Option<Pubkey>,
+    
+    /// For CLMM LP strategies: vault-owned position in `pool_address`
+    pub clmm_position: Option<Pubkey>,
+    
+    /// Lower tick of the position's active range (inclusive)
+    pub tick_lower: i32,
+    
+    /// Upper tick of the position's active range (exclusive)
+    pub tick_upper: i32,
+    
+    /// For dual-asset vaults: oracle price feed for token B (Pyth)
+    pub token_b_oracle_price_feed: Option<Pubkey>,
+    
+    /// For liquid staking: SPL stake pool whose pool mint is the underlying LST
+    pub stake_pool: Option<Pubkey>,
+    
     /// Reserve space for future strategy params
-    pub reserved: [u8; 64],
+    pub reserved: [[u8; 32]; 2], // 64 bytes, split so Default derives
 }
 
 impl StrategyConfig {
@@ -182,9 +351,48 @@ impl StrategyConfig {
         4 * 2 +  // current_allocations [u16; 4]
         1 + 32 + // oracle_price_feed (Option<Pubkey>)
         1 + 2 +  // liquidation_threshold_bps (Option<u16>)
+        1 + 2 +  // close_factor_bps (Option<u16>)
+        1 + 2 +  // liquidation_bonus_bps (Option<u16>)
+        1 + 2 +  // liquidation_protocol_fee_bps (Option<u16>)
+        1 + 2 +  // auction_max_bonus_bps (Option<u16>)
+        1 + 8 +  // auction_duration (Option<i64>)
+        1 + 2 +  // deleverage_threshold_bps (Option<u16>)
+        1 + 2 +  // deleverage_target_leverage_bps (Option<u16>)
+        1 + 2 +  // keeper_fee_bps (Option<u16>)
+        1 + 32 + // swap_program (Option<Pubkey>)
+        2 +      // max_swap_slippage_bps
+        4 * RewardMintConfig::LEN + // reward_mints
+        1 + 32 + // strategy_adapter (Option<Pubkey>)
+        1 + 32 + // lending_market (Option<Pubkey>)
+        1 + 32 + // debt_mint (Option<Pubkey>)
+        1 + 32 + // debt_oracle_price_feed (Option<Pubkey>)
+        1 + 32 + // perp_program (Option<Pubkey>)
+        1 + 32 + // perp_market (Option<Pubkey>)
+        1 + 2 +  // rehedge_band_bps (Option<u16>)
+        1 + 32 + // clmm_program (Option<Pubkey>)
+        1 + 32 + // clmm_position (Option<Pubkey>)
+        4 +      // tick_lower
+        4 +      // tick_upper
+        1 + 32 + // token_b_oracle_price_feed (Option<Pubkey>)
+        1 + 32 + // stake_pool (Option<Pubkey>)
         64;      // reserved
 }
 
+/// Reward token accepted by harvest and swapped into the underlying mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardMintConfig {
+    /// Reward token mint (default pubkey = unused slot)
+    pub mint: Pubkey,
+    
+    /// Oracle price feed for the reward token (Pyth)
+    pub oracle_price_feed: Pubkey,
+}
+
+impl RewardMintConfig {
+    pub const LEN: usize = 32 + // mint
+        32;  // oracle_price_feed
+}
+
 /// User position tracking (optional, for advanced features like leverage tracking per user)
 #[account]
 pub struct UserPosition {
@@ -206,9 +414,27 @@ pub struct UserPosition {
     /// User's debt amount (for leveraged positions)
     pub debt: u64,
     
+    /// Lending obligation holding this position's collateral and debt (default = none)
+    pub obligation: Pubkey,
+    
+    /// Start of the active liquidation auction (0 = none)
+    pub liquidation_auction_start: i64,
+    
     /// Last interaction timestamp
     pub last_interaction: i64,
     
+    /// Lifetime assets deposited (vault accounting units)
+    pub total_deposited: u64,
+    
+    /// Lifetime assets withdrawn (vault accounting units)
+    pub total_withdrawn: u64,
+    
+    /// Share-weighted average share price paid for `shares` (scaled by SHARE_PRICE_PRECISION)
+    pub avg_entry_share_price: u64,
+    
+    /// PnL realized by withdrawals against the average entry price (vault accounting units)
+    pub realized_pnl: i64,
+    
     /// Bump seed
     pub bump: u8,
 }
@@ -221,7 +447,155 @@ impl UserPosition {
         2 +  // leverage_bps
         8 +  // collateral
         8 +  // debt
+        32 + // obligation
+        8 +  // liquidation_auction_start
         8 +  // last_interaction
+        8 +  // total_deposited
+        8 +  // total_withdrawn
+        8 +  // avg_entry_share_price
+        8 +  // realized_pnl
+        1;   // bump
+}
+
+/// Per-user share lock (boosted rewards, no withdrawal before expiry)
+#[account]
+pub struct UserLock {
+    /// User wallet
+    pub user: Pubkey,
+    
+    /// Vault this lock belongs to
+    pub vault: Pubkey,
+    
+    /// Shares held in the lock escrow for this user
+    pub shares: u64,
+    
+    /// Tier-weighted shares (shares * tier multiplier)
+    pub weight: u64,
+    
+    /// Lock tier (index into LOCK_TIERS)
+    pub tier: u8,
+    
+    /// Timestamp after which the lock can be released without penalty
+    pub unlock_ts: i64,
+    
+    /// weight * acc_boost_per_weight at the last settlement
+    pub boost_debt: u128,
+    
+    /// Boost shares settled but not yet minted
+    pub pending_boost_shares: u64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl UserLock {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        32 + // vault
+        8 +  // shares
+        8 +  // weight
+        1 +  // tier
+        8 +  // unlock_ts
+        16 + // boost_debt
+        8 +  // pending_boost_shares
+        1;   // bump
+}
+
+/// Maximum number of concurrent reward emissions per vault
+pub const MAX_REWARD_EMISSIONS: usize = 4;
+
+/// Emission schedule of one secondary reward mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardEmission {
+    /// Reward token mint (default pubkey = empty slot)
+    pub reward_mint: Pubkey,
+    
+    /// Vault-owned token account holding funded rewards
+    pub reward_vault: Pubkey,
+    
+    /// Reward tokens emitted per second across all reward shares
+    pub emissions_per_second: u64,
+    
+    /// Emission start timestamp
+    pub start_ts: i64,
+    
+    /// Emission end timestamp
+    pub end_ts: i64,
+    
+    /// Timestamp up to which emissions have been accrued
+    pub last_update_ts: i64,
+    
+    /// Reward tokens accrued per reward share (scaled by REWARD_PRECISION)
+    pub acc_reward_per_share: u128,
+    
+    /// Lifetime reward tokens claimed
+    pub total_claimed: u64,
+}
+
+impl RewardEmission {
+    pub const LEN: usize = 32 + // reward_mint
+        32 + // reward_vault
+        8 +  // emissions_per_second
+        8 +  // start_ts
+        8 +  // end_ts
+        8 +  // last_update_ts
+        16 + // acc_reward_per_share
+        8;   // total_claimed
+}
+
+/// Secondary reward emissions (liquidity mining) for a vault's share holders
+#[account]
+pub struct RewardDistributor {
+    /// Vault whose share holders earn the emissions
+    pub vault: Pubkey,
+    
+    /// Sum of all opted-in holders' reward shares
+    pub total_shares: u64,
+    
+    /// Emission schedules, one per reward mint
+    pub emissions: [RewardEmission; MAX_REWARD_EMISSIONS],
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl RewardDistributor {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // vault
+        8 +  // total_shares
+        MAX_REWARD_EMISSIONS * RewardEmission::LEN + // emissions
+        1;   // bump
+}
+
+/// Per-user emission rewards, checkpointed on every share balance change
+#[account]
+pub struct UserRewards {
+    /// User wallet
+    pub user: Pubkey,
+    
+    /// Vault these rewards belong to
+    pub vault: Pubkey,
+    
+    /// Shares earning emissions (held plus locked)
+    pub shares: u64,
+    
+    /// shares * acc_reward_per_share at the last checkpoint, per emission
+    pub reward_debts: [u128; MAX_REWARD_EMISSIONS],
+    
+    /// Rewards settled but not yet claimed, per emission
+    pub pending_rewards: [u64; MAX_REWARD_EMISSIONS],
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl UserRewards {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        32 + // vault
+        8 +  // shares
+        MAX_REWARD_EMISSIONS * 16 + // reward_debts
+        MAX_REWARD_EMISSIONS * 8 +  // pending_rewards
         1;   // bump
 }
 
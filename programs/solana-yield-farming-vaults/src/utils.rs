@@ -3,11 +3,13 @@ use crate::errors::VaultError;
 use crate::state::Vault;
 
 /// Calculate shares to mint for a given deposit amount
-/// Formula: shares = (deposit * total_shares) / total_assets
+/// Formula: shares = (deposit * total_shares) / (total_assets - locked_profit)
 /// If vault is empty: shares = deposit (1:1 initial ratio)
+/// Locked (not yet vested) profit is excluded so harvests cannot be front-run
 pub fn calculate_shares_to_mint(
     deposit_amount: u64,
     total_assets: u64,
+    locked_profit: u64,
     total_shares: u64,
 ) -> Result<u64> {
     if total_shares == 0 {
@@ -15,37 +17,105 @@ pub fn calculate_shares_to_mint(
         return Ok(deposit_amount);
     }
     
-    if total_assets == 0 {
+    let free_assets = total_assets.saturating_sub(locked_profit);
+    
+    if free_assets == 0 {
         return Err(VaultError::MathOverflow.into());
     }
     
-    // shares = (deposit * total_shares) / total_assets
+    // shares = (deposit * total_shares) / free_assets
     // Use checked math to prevent overflow
     deposit_amount
         .checked_mul(total_shares)
         .ok_or(VaultError::MathOverflow)?
-        .checked_div(total_assets)
+        .checked_div(free_assets)
         .ok_or(VaultError::MathOverflow.into())
 }
 
 /// Calculate assets to withdraw for a given number of shares
-/// Formula: assets = (shares * total_assets) / total_shares
+/// Formula: assets = (shares * (total_assets - locked_profit)) / total_shares
 pub fn calculate_assets_from_shares(
     shares: u64,
     total_assets: u64,
+    locked_profit: u64,
     total_shares: u64,
 ) -> Result<u64> {
     if total_shares == 0 {
         return Err(VaultError::MathOverflow.into());
     }
     
+    let free_assets = total_assets.saturating_sub(locked_profit);
+    
     shares
-        .checked_mul(total_assets)
+        .checked_mul(free_assets)
         .ok_or(VaultError::MathOverflow)?
         .checked_div(total_shares)
         .ok_or(VaultError::MathOverflow.into())
 }
 
+/// Calculate the portion of harvested profit that is still locked
+/// Profit vests linearly over profit_unlock_duration starting at the last harvest
+/// Formula: locked = locked_profit * (duration - elapsed) / duration
+pub fn calculate_locked_profit(vault: &Vault, current_timestamp: i64) -> Result<u64> {
+    let elapsed = current_timestamp
+        .checked_sub(vault.last_harvest)
+        .ok_or(VaultError::InvalidTimestamp)?;
+    
+    if vault.profit_unlock_duration <= 0 || elapsed >= vault.profit_unlock_duration {
+        return Ok(0);
+    }
+    
+    let remaining = vault.profit_unlock_duration
+        .checked_sub(elapsed.max(0))
+        .ok_or(VaultError::MathOverflow)? as u128;
+    
+    let locked = (vault.locked_profit as u128)
+        .checked_mul(remaining)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(vault.profit_unlock_duration as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok(locked as u64)
+}
+
+/// Draw down locked profit to absorb a loss, returning the amount absorbed
+/// The stored value is rescaled so the remaining lock keeps vesting on the same schedule
+pub fn absorb_loss_with_locked_profit(
+    vault: &mut Vault,
+    loss: u64,
+    current_timestamp: i64,
+) -> Result<u64> {
+    let locked_now = calculate_locked_profit(vault, current_timestamp)?;
+    if locked_now == 0 {
+        vault.locked_profit = 0;
+        return Ok(0);
+    }
+    
+    let absorbed = loss.min(locked_now);
+    let locked_after = locked_now
+        .checked_sub(absorbed)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    // stored = locked_after * duration / remaining
+    let elapsed = current_timestamp
+        .checked_sub(vault.last_harvest)
+        .ok_or(VaultError::InvalidTimestamp)?
+        .max(0);
+    let remaining = vault.profit_unlock_duration
+        .checked_sub(elapsed)
+        .ok_or(VaultError::MathOverflow)? as u128;
+    
+    let stored = (locked_after as u128)
+        .checked_mul(vault.profit_unlock_duration as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(remaining)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    vault.locked_profit = u64::try_from(stored).map_err(|_| VaultError::MathOverflow)?;
+    
+    Ok(absorbed)
+}
+
 /// Calculate management fee accrued over time
 /// Formula: fee = total_assets * management_fee_bps * time_elapsed / (10000 * seconds_per_year)
 pub fn calculate_management_fee(
@@ -113,6 +183,78 @@ pub fn calculate_health_factor(
         .ok_or(VaultError::MathOverflow.into())
 }
 
+/// Calculate oracle-priced health factor for a looped leveraged position
+/// Collateral and debt may be different mints; the debt leg is valued in the
+/// collateral mint's decimals. Borrowed liquidity is redeposited, so
+/// supplied value = collateral value + debt value
+/// Formula: health = (collateral_value + debt_value) * collateral_factor / debt_value
+pub fn calculate_position_health_factor(
+    collateral: u64,
+    collateral_price: i64,
+    collateral_decimals: u8,
+    debt: u64,
+    debt_price: i64,
+    debt_decimals: u8,
+    collateral_factor_bps: u16,
+) -> Result<u64> {
+    if debt == 0 {
+        return Ok(u64::MAX); // No debt = infinite health
+    }
+    
+    require!(collateral_price > 0 && debt_price > 0, VaultError::InvalidOracle);
+    
+    let collateral_value = (collateral as u128)
+        .checked_mul(collateral_price as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    let debt_value = scale_decimals(
+        (debt as u128)
+            .checked_mul(debt_price as u128)
+            .ok_or(VaultError::MathOverflow)?,
+        debt_decimals,
+        collateral_decimals,
+    )?;
+    
+    if debt_value == 0 {
+        return Ok(u64::MAX); // Dust debt rounds to zero value
+    }
+    
+    let health = collateral_value
+        .checked_add(debt_value)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_mul(collateral_factor_bps as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(debt_value)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok(u64::try_from(health).unwrap_or(u64::MAX))
+}
+
+/// Get validated oracle prices for a leveraged position's (collateral, debt) legs
+/// Without a collateral oracle both legs are treated as the underlying at par;
+/// a debt mint other than the underlying requires its own oracle
+pub fn get_position_prices(vault: &Vault) -> Result<(i64, i64)> {
+    let config = &vault.strategy_config;
+    
+    let collateral_price = match config.oracle_price_feed {
+        Some(oracle) => {
+            require!(validate_oracle_price(&oracle, 300)?, VaultError::StaleOraclePrice);
+            get_oracle_price(&oracle)?
+        }
+        None => 100_000_000, // 1.0 (1e8 scale)
+    };
+    
+    let debt_price = match config.debt_mint {
+        Some(debt_mint) if debt_mint != vault.underlying_mint => {
+            let oracle = config.debt_oracle_price_feed.ok_or(VaultError::InvalidOracle)?;
+            require!(validate_oracle_price(&oracle, 300)?, VaultError::StaleOraclePrice);
+            get_oracle_price(&oracle)?
+        }
+        _ => collateral_price,
+    };
+    
+    Ok((collateral_price, debt_price))
+}
+
 /// Check if rebalance is needed based on current vs target allocations
 /// Returns true if deviation exceeds threshold
 pub fn should_rebalance(
@@ -123,7 +265,7 @@ pub fn should_rebalance(
     for i in 0..4 {
         let current = current_allocations[i] as i32;
         let target = target_allocations[i] as i32;
-        let deviation = (current - target).abs() as u16;
+        let deviation = (current - target).unsigned_abs() as u16;
         
         if deviation > threshold_bps {
             return true;
@@ -182,6 +324,525 @@ pub fn get_oracle_price(_oracle_account: &Pubkey) -> Result<i64> {
     Ok(100_000_000)
 }
 
+/// Rescale a token amount from one mint's decimals to another's
+pub fn scale_decimals(
+    amount: u128,
+    from_decimals: u8,
+    to_decimals: u8,
+) -> Result<u128> {
+    if to_decimals >= from_decimals {
+        let factor = 10u128
+            .checked_pow((to_decimals - from_decimals) as u32)
+            .ok_or(VaultError::MathOverflow)?;
+        amount
+            .checked_mul(factor)
+            .ok_or(VaultError::MathOverflow.into())
+    } else {
+        let factor = 10u128
+            .checked_pow((from_decimals - to_decimals) as u32)
+            .ok_or(VaultError::MathOverflow)?;
+        amount
+            .checked_div(factor)
+            .ok_or(VaultError::MathOverflow.into())
+    }
+}
+
+/// Convert a token amount into another mint at oracle prices
+/// Formula: amount_out = amount_in * price_in / price_out
+/// (amounts rescaled from the input mint's decimals to the output mint's decimals)
+pub fn convert_at_oracle_price(
+    amount_in: u64,
+    price_in: i64,
+    decimals_in: u8,
+    price_out: i64,
+    decimals_out: u8,
+) -> Result<u64> {
+    require!(price_in > 0 && price_out > 0, VaultError::InvalidOracle);
+    
+    let value_in = (amount_in as u128)
+        .checked_mul(price_in as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    let amount_out = scale_decimals(value_in, decimals_in, decimals_out)?
+        .checked_div(price_out as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(amount_out).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Value both legs of a dual-asset vault in token A units
+/// Formula: value = amount_a + amount_b * price_b / price_a
+pub fn calculate_pair_value(
+    amount_a: u64,
+    amount_b: u64,
+    price_a: i64,
+    decimals_a: u8,
+    price_b: i64,
+    decimals_b: u8,
+) -> Result<u64> {
+    let value_b = convert_at_oracle_price(amount_b, price_b, decimals_b, price_a, decimals_a)?;
+    
+    amount_a
+        .checked_add(value_b)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Largest amounts of both tokens matching the vault's current token ratio
+/// Formula: b = a * total_b / total_a, capped at amount_b (and a rescaled)
+/// An empty vault accepts both amounts as given
+pub fn calculate_matched_pair_amounts(
+    amount_a: u64,
+    amount_b: u64,
+    total_a: u64,
+    total_b: u64,
+) -> Result<(u64, u64)> {
+    if total_a == 0 && total_b == 0 {
+        return Ok((amount_a, amount_b));
+    }
+    if total_b == 0 {
+        return Ok((amount_a, 0));
+    }
+    if total_a == 0 {
+        return Ok((0, amount_b));
+    }
+    
+    let matched_b = (amount_a as u128)
+        .checked_mul(total_b as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(total_a as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    if matched_b <= amount_b as u128 {
+        return Ok((amount_a, matched_b as u64));
+    }
+    
+    let matched_a = (amount_b as u128)
+        .checked_mul(total_a as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(total_b as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok((matched_a as u64, amount_b))
+}
+
+/// Calculate the oracle-bounded minimum output for a swap
+/// Formula: min_out = amount_in * price_in / price_out * (10000 - max_slippage_bps) / 10000
+pub fn calculate_min_swap_output(
+    amount_in: u64,
+    price_in: i64,
+    decimals_in: u8,
+    price_out: i64,
+    decimals_out: u8,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    let expected_out = convert_at_oracle_price(
+        amount_in,
+        price_in,
+        decimals_in,
+        price_out,
+        decimals_out,
+    )?;
+    
+    let min_out = (expected_out as u128)
+        .checked_mul(10000u128.saturating_sub(max_slippage_bps as u128))
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(min_out).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate the swap input whose oracle-bounded minimum output covers `amount_out`
+/// Formula: max_in = amount_out * price_out / price_in * 10000 / (10000 - max_slippage_bps), rounded up
+pub fn calculate_max_swap_input(
+    amount_out: u64,
+    price_in: i64,
+    decimals_in: u8,
+    price_out: i64,
+    decimals_out: u8,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    require!(max_slippage_bps < 10000, VaultError::SlippageExceeded);
+    
+    let expected_in = convert_at_oracle_price(
+        amount_out,
+        price_out,
+        decimals_out,
+        price_in,
+        decimals_in,
+    )?;
+    
+    let kept_bps = 10000u128 - max_slippage_bps as u128;
+    let max_in = (expected_in as u128)
+        .checked_add(1) // Covers rounding in the price conversion
+        .ok_or(VaultError::MathOverflow)?
+        .checked_mul(10000)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(kept_bps - 1)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(kept_bps)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(max_in).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate the current Dutch-auction liquidation bonus
+/// Formula: bonus = 10000 + (max_bonus - 10000) * min(elapsed, duration) / duration
+pub fn calculate_auction_bonus(
+    auction_start: i64,
+    current_timestamp: i64,
+    max_bonus_bps: u16,
+    duration: i64,
+) -> Result<u16> {
+    require!(duration > 0, VaultError::InvalidTimestamp);
+    
+    let elapsed = current_timestamp
+        .checked_sub(auction_start)
+        .ok_or(VaultError::MathOverflow)?
+        .clamp(0, duration);
+    let max_premium = (max_bonus_bps as i64).saturating_sub(10000);
+    
+    let premium = max_premium
+        .checked_mul(elapsed)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(duration)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok((10000 + premium) as u16)
+}
+
+/// Calculate leverage from collateral and debt (debt valued in collateral units)
+/// Formula: leverage = (collateral + debt) * 10000 / collateral
+pub fn calculate_leverage_bps(
+    collateral: u64,
+    debt_in_collateral: u64,
+) -> Result<u16> {
+    if collateral == 0 {
+        return Ok(10000); // 1x
+    }
+    
+    let leverage = (collateral as u128)
+        .checked_add(debt_in_collateral as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_mul(10000)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(collateral as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok(leverage.min(u16::MAX as u128) as u16)
+}
+
+/// Calculate net delta of a hedged vault (spot exposure offset by the perp hedge)
+/// Formula: net_delta = spot_exposure + hedge_size (hedge_size < 0 for a short)
+pub fn calculate_net_delta(
+    spot_exposure: u64,
+    hedge_size: i64,
+) -> Result<i64> {
+    i64::try_from(spot_exposure)
+        .map_err(|_| VaultError::MathOverflow)?
+        .checked_add(hedge_size)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Calculate the spot leg of a hedged vault: assets neither idle in the vault
+/// token account nor posted as perp margin
+/// Formula: spot_exposure = total_assets - idle_balance - perp_margin
+pub fn calculate_spot_exposure(
+    total_assets: u64,
+    idle_balance: u64,
+    perp_margin: u64,
+) -> u64 {
+    total_assets
+        .saturating_sub(idle_balance)
+        .saturating_sub(perp_margin)
+}
+
+/// Calculate the worst acceptable fill price for a perp order
+/// Sells accept down to the oracle price minus slippage, buys pay up to the
+/// oracle price plus slippage
+/// Formula: limit_price = oracle_price * (10000 -/+ max_slippage_bps) / 10000
+pub fn calculate_perp_limit_price(
+    oracle_price: i64,
+    base_delta: i64,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    require!(oracle_price > 0, VaultError::InvalidOracle);
+    
+    let factor = if base_delta < 0 {
+        10000u128.saturating_sub(max_slippage_bps as u128)
+    } else {
+        10000u128
+            .checked_add(max_slippage_bps as u128)
+            .ok_or(VaultError::MathOverflow)?
+    };
+    let limit_price = (oracle_price as u128)
+        .checked_mul(factor)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(limit_price).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Check whether a vault's net delta has drifted outside the rehedge band
+/// Returns true if |net_delta| / spot_exposure exceeds band_bps
+pub fn should_rehedge(
+    net_delta: i64,
+    spot_exposure: u64,
+    band_bps: u16,
+) -> Result<bool> {
+    if spot_exposure == 0 {
+        return Ok(net_delta != 0);
+    }
+    
+    let deviation_bps = (net_delta.unsigned_abs() as u128)
+        .checked_mul(10000)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(spot_exposure as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok(deviation_bps > band_bps as u128)
+}
+
+/// Fixed-point scale of a liquid staking token exchange rate
+/// (lamports per 10^9 LST base units)
+pub const STAKE_RATE_PRECISION: u64 = 1_000_000_000;
+
+/// Calculate a stake pool's exchange rate from its totals
+/// Formula: rate = total_lamports * STAKE_RATE_PRECISION / pool_token_supply
+pub fn calculate_stake_rate(
+    total_lamports: u64,
+    pool_token_supply: u64,
+) -> Result<u64> {
+    if pool_token_supply == 0 {
+        // Empty pool: pool tokens are minted 1:1
+        return Ok(STAKE_RATE_PRECISION);
+    }
+    
+    let rate = (total_lamports as u128)
+        .checked_mul(STAKE_RATE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(pool_token_supply as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(rate).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Convert underlying token units into the vault's accounting units
+/// Liquid staking vaults account in SOL lamports at `lst_exchange_rate`;
+/// every other vault accounts in the underlying token itself
+/// Formula: assets = amount * lst_exchange_rate / STAKE_RATE_PRECISION
+pub fn underlying_to_assets(vault: &Vault, amount: u64) -> Result<u64> {
+    if vault.lst_exchange_rate == 0 {
+        return Ok(amount);
+    }
+    
+    let assets = (amount as u128)
+        .checked_mul(vault.lst_exchange_rate as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(STAKE_RATE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(assets).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Convert vault accounting units into underlying token units (rounds down)
+/// Formula: amount = assets * STAKE_RATE_PRECISION / lst_exchange_rate
+pub fn assets_to_underlying(vault: &Vault, assets: u64) -> Result<u64> {
+    if vault.lst_exchange_rate == 0 {
+        return Ok(assets);
+    }
+    
+    let amount = (assets as u128)
+        .checked_mul(STAKE_RATE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(vault.lst_exchange_rate as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(amount).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Fixed-point scale of per-share prices tracked on user positions
+pub const SHARE_PRICE_PRECISION: u64 = 1_000_000_000;
+
+/// Calculate the price paid or received per share
+/// Formula: price = assets * SHARE_PRICE_PRECISION / shares
+pub fn calculate_share_price(
+    assets: u64,
+    shares: u64,
+) -> Result<u64> {
+    if shares == 0 {
+        return Ok(0);
+    }
+    
+    let price = (assets as u128)
+        .checked_mul(SHARE_PRICE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(shares as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(price).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate the cost basis of shares bought at an average entry price
+/// Formula: cost = shares * avg_entry_share_price / SHARE_PRICE_PRECISION
+pub fn calculate_cost_basis(
+    shares: u64,
+    avg_entry_share_price: u64,
+) -> Result<u64> {
+    let cost = (shares as u128)
+        .checked_mul(avg_entry_share_price as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(SHARE_PRICE_PRECISION as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(cost).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate the share-weighted average entry price after buying more shares
+/// Formula: avg = (held * avg_price + bought * price) / (held + bought)
+pub fn calculate_avg_entry_price(
+    shares_held: u64,
+    avg_entry_share_price: u64,
+    shares_bought: u64,
+    price: u64,
+) -> Result<u64> {
+    let total_shares = (shares_held as u128)
+        .checked_add(shares_bought as u128)
+        .ok_or(VaultError::MathOverflow)?;
+    if total_shares == 0 {
+        return Ok(0);
+    }
+    
+    let weighted = (shares_held as u128)
+        .checked_mul(avg_entry_share_price as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_add(
+            (shares_bought as u128)
+                .checked_mul(price as u128)
+                .ok_or(VaultError::MathOverflow)?,
+        )
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(weighted / total_shares).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Fixed-point scale of boost shares accrued per unit of lock weight
+pub const BOOST_PRECISION: u128 = 1_000_000_000_000;
+
+/// Calculate the reward weight of locked shares
+/// Formula: weight = shares * multiplier_bps / 10000
+pub fn calculate_lock_weight(
+    shares: u64,
+    multiplier_bps: u16,
+) -> Result<u64> {
+    let weight = (shares as u128)
+        .checked_mul(multiplier_bps as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(weight).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate a lock's boost debt at the current accumulator
+/// Formula: debt = weight * acc_boost_per_weight
+pub fn calculate_boost_debt(
+    weight: u64,
+    acc_boost_per_weight: u128,
+) -> Result<u128> {
+    (weight as u128)
+        .checked_mul(acc_boost_per_weight)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Calculate boost shares accrued to a lock since its last settlement
+/// Formula: pending = (weight * acc_boost_per_weight - debt) / BOOST_PRECISION
+pub fn calculate_pending_boost(
+    weight: u64,
+    acc_boost_per_weight: u128,
+    boost_debt: u128,
+) -> Result<u64> {
+    let pending = calculate_boost_debt(weight, acc_boost_per_weight)?
+        .checked_sub(boost_debt)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(BOOST_PRECISION)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(pending).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Fixed-point scale of emission rewards accrued per reward share
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Calculate the increase of an emission's reward-per-share accumulator
+/// Formula: increase = emissions_per_second * elapsed * REWARD_PRECISION / total_shares
+pub fn calculate_reward_accrual(
+    emissions_per_second: u64,
+    elapsed: i64,
+    total_shares: u64,
+) -> Result<u128> {
+    if total_shares == 0 || elapsed <= 0 {
+        return Ok(0);
+    }
+    
+    (emissions_per_second as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_mul(REWARD_PRECISION)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(total_shares as u128)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Calculate a user's reward debt at the current accumulator
+/// Formula: debt = shares * acc_reward_per_share
+pub fn calculate_reward_debt(
+    shares: u64,
+    acc_reward_per_share: u128,
+) -> Result<u128> {
+    (shares as u128)
+        .checked_mul(acc_reward_per_share)
+        .ok_or(VaultError::MathOverflow.into())
+}
+
+/// Calculate emission rewards accrued to a user since their last checkpoint
+/// Formula: pending = (shares * acc_reward_per_share - debt) / REWARD_PRECISION
+pub fn calculate_pending_reward(
+    shares: u64,
+    acc_reward_per_share: u128,
+    reward_debt: u128,
+) -> Result<u64> {
+    let pending = calculate_reward_debt(shares, acc_reward_per_share)?
+        .checked_sub(reward_debt)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(REWARD_PRECISION)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    u64::try_from(pending).map_err(|_| VaultError::MathOverflow.into())
+}
+
+/// Calculate a tick range of the same width centered on the current tick
+/// Formula: lower = tick_current - width / 2, upper = lower + width
+pub fn calculate_centered_range(
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(i32, i32)> {
+    let width = tick_upper
+        .checked_sub(tick_lower)
+        .ok_or(VaultError::MathOverflow)?;
+    require!(width > 0, VaultError::InvalidTickRange);
+    
+    let new_lower = tick_current
+        .checked_sub(width / 2)
+        .ok_or(VaultError::MathOverflow)?;
+    let new_upper = new_lower
+        .checked_add(width)
+        .ok_or(VaultError::MathOverflow)?;
+    
+    Ok((new_lower, new_upper))
+}
+
 /// Calculate leverage-adjusted position size
 /// Formula: position = collateral * leverage_bps / 10000
 pub fn calculate_leveraged_position(
@@ -1,13 +1,25 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { SolanaYieldFarmingVaults } from "../target/types/solana_yield_farming_vaults";
+import { MockLending } from "../target/types/mock_lending";
+import { MockClmm } from "../target/types/mock_clmm";
+import { MockPerp } from "../target/types/mock_perp";
+import { MockSwap } from "../target/types/mock_swap";
 import { 
   TOKEN_PROGRAM_ID, 
+  TOKEN_2022_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddress,
   createMint,
   createAccount,
   mintTo,
+  getMint,
+  getAccount,
+  getTokenMetadata,
+  getTransferHook,
+  transferCheckedWithTransferHook,
+  getOrCreateAssociatedTokenAccount,
+  NATIVE_MINT,
 } from "@solana/spl-token";
 import { expect } from "chai";
 
@@ -17,6 +29,10 @@ describe("solana-yield-farming-vaults", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.SolanaYieldFarmingVaults as Program<SolanaYieldFarmingVaults>;
+  const mockLending = anchor.workspace.MockLending as Program<MockLending>;
+  const mockClmm = anchor.workspace.MockClmm as Program<MockClmm>;
+  const mockPerp = anchor.workspace.MockPerp as Program<MockPerp>;
+  const mockSwap = anchor.workspace.MockSwap as Program<MockSwap>;
   const wallet = provider.wallet;
 
   // Test accounts
@@ -34,6 +50,243 @@ describe("solana-yield-farming-vaults", () => {
     program.programId
   );
 
+  // update_vault_params with every field left unchanged
+  const noParamChanges = {
+    managementFeeBps: null,
+    performanceFeeBps: null,
+    harvestCooldown: null,
+    rebalanceCooldown: null,
+    rebalanceThresholdBps: null,
+    minDeposit: null,
+    harvestBountyBps: null,
+    profitUnlockDuration: null,
+    maxLossReportBps: null,
+    lossPauseThresholdBps: null,
+    reserveFeeBps: null,
+  };
+
+  // Create another vault on the underlying test mint, with a share account for the test wallet
+  const createTestVault = async (
+    strategy: number,
+    maxLeverageBps: number | null,
+    name: string,
+    symbol: string
+  ) => {
+    const vaultId = (await program.account.globalState.fetch(globalStatePda)).vaultCount;
+    const [testVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [testShareMint] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), testVault.toBuffer()],
+      program.programId
+    );
+    const testVaultTokenAccount = await getAssociatedTokenAddress(underlyingMint, testVault, true);
+
+    await program.methods
+      .initializeVault(strategy, null, null, maxLeverageBps, new anchor.BN(1000000), name, symbol, "")
+      .accounts({
+        vault: testVault,
+        globalState: globalStatePda,
+        underlyingMint: underlyingMint,
+        shareMint: testShareMint,
+        vaultTokenAccount: testVaultTokenAccount,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const testShareAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      testShareMint,
+      wallet.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    return {
+      vault: testVault,
+      shareMint: testShareMint,
+      vaultTokenAccount: testVaultTokenAccount,
+      userShareAccount: testShareAccount.address,
+    };
+  };
+
+  // Mint underlying tokens to the test wallet and deposit them into a test vault
+  const depositInto = async (
+    target: Awaited<ReturnType<typeof createTestVault>>,
+    amount: anchor.BN
+  ) => {
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      userTokenAccount,
+      wallet.publicKey,
+      BigInt(amount.toString())
+    );
+
+    await program.methods
+      .deposit(amount)
+      .accounts({
+        vault: target.vault,
+        vaultTokenAccount: target.vaultTokenAccount,
+        userTokenAccount: userTokenAccount,
+        underlyingMint: underlyingMint,
+        shareMint: target.shareMint,
+        userShareAccount: target.userShareAccount,
+        user: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+  };
+
+  // Change some fields of a vault's strategy config, keeping the rest
+  const updateStrategy = async (target: anchor.web3.PublicKey, changes: object) => {
+    const { strategyConfig } = await program.account.vault.fetch(target);
+
+    await program.methods
+      .updateStrategyConfig({ ...strategyConfig, ...changes })
+      .accounts({
+        vault: target,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+  };
+
+  // Debt-leg accounts of a leveraged vault that borrows its own underlying mint
+  const sameAssetDebtAccounts = {
+    debtMint: null,
+    vaultDebtTokenAccount: null,
+    debtLiquiditySupply: null,
+    swapProgram: null,
+    swapPool: null,
+    swapPoolSource: null,
+    swapPoolDestination: null,
+  };
+
+  // Create a leveraged test vault on the mock lending market (created by the
+  // leverage test) with `idle` underlying deposited to repay debt from
+  const createLeveragedVault = async (name: string, symbol: string, idle: anchor.BN) => {
+    const leveraged = await createTestVault(1, 30000, name, symbol);
+    const [lendingMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), underlyingMint.toBuffer()],
+      mockLending.programId
+    );
+    const [liquiditySupply] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("supply"), lendingMarket.toBuffer()],
+      mockLending.programId
+    );
+
+    await updateStrategy(leveraged.vault, {
+      lendingProtocol: mockLending.programId,
+      lendingMarket: lendingMarket,
+    });
+    await depositInto(leveraged, idle);
+
+    return { ...leveraged, lendingMarket, liquiditySupply };
+  };
+
+  // Open `user`'s position on a leveraged test vault, supplying `collateral` at `leverageBps`
+  const openLeveragedPosition = async (
+    leveraged: Awaited<ReturnType<typeof createLeveragedVault>>,
+    user: anchor.web3.Keypair,
+    userToken: anchor.web3.PublicKey,
+    collateral: anchor.BN,
+    leverageBps: number
+  ) => {
+    const [userPosition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_position"), leveraged.vault.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [obligation] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("obligation"),
+        leveraged.lendingMarket.toBuffer(),
+        leveraged.vault.toBuffer(),
+        userPosition.toBuffer(),
+      ],
+      mockLending.programId
+    );
+
+    await program.methods
+      .initializeUserPosition()
+      .accounts({
+        vault: leveraged.vault,
+        userPosition: userPosition,
+        user: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lendingProgram: mockLending.programId,
+        lendingMarket: leveraged.lendingMarket,
+        obligation: obligation,
+      })
+      .signers([user])
+      .rpc();
+
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      userToken,
+      wallet.publicKey,
+      BigInt(collateral.toString())
+    );
+
+    const leverageAccounts = {
+      vault: leveraged.vault,
+      userPosition: userPosition,
+      vaultTokenAccount: leveraged.vaultTokenAccount,
+      userTokenAccount: userToken,
+      user: user.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      underlyingMint: underlyingMint,
+      lendingProgram: mockLending.programId,
+      lendingMarket: leveraged.lendingMarket,
+      obligation: obligation,
+      liquiditySupply: leveraged.liquiditySupply,
+      ...sameAssetDebtAccounts,
+    };
+    await program.methods
+      .adjustLeverage(leverageBps, collateral, new anchor.BN(0))
+      .accounts(leverageAccounts)
+      .signers([user])
+      .rpc();
+
+    return { userPosition, obligation, leverageAccounts };
+  };
+
+  // A funded signer other than the test wallet (liquidator, keeper, second borrower)
+  const createActor = async (tokens: number) => {
+    const actor = anchor.web3.Keypair.generate();
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: wallet.publicKey,
+          toPubkey: actor.publicKey,
+          lamports: anchor.web3.LAMPORTS_PER_SOL,
+        })
+      )
+    );
+    const tokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      actor.publicKey
+    )).address;
+    if (tokens > 0) {
+      await mintTo(provider.connection, wallet.payer, underlyingMint, tokenAccount, wallet.publicKey, tokens);
+    }
+
+    return { keypair: actor, tokenAccount };
+  };
+
   before(async () => {
     // Initialize test mints and accounts
     treasury = wallet.publicKey;
@@ -47,15 +300,6 @@ describe("solana-yield-farming-vaults", () => {
       9 // 9 decimals
     );
 
-    // Create share token mint
-    shareMint = await createMint(
-      provider.connection,
-      wallet.payer,
-      wallet.publicKey,
-      null,
-      9
-    );
-
     // Get vault PDA
     const [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from("vault"), Buffer.from([0, 0, 0, 0, 0, 0, 0, 0])],
@@ -63,6 +307,13 @@ describe("solana-yield-farming-vaults", () => {
     );
     vault = vaultPda;
 
+    // Share mint is created by initialize_vault as a vault-derived PDA
+    const [shareMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), vault.toBuffer()],
+      program.programId
+    );
+    shareMint = shareMintPda;
+
     // Get vault token account
     vaultTokenAccount = await getAssociatedTokenAddress(
       underlyingMint,
@@ -71,15 +322,19 @@ describe("solana-yield-farming-vaults", () => {
     );
 
     // Create user token account
-    userTokenAccount = await getAssociatedTokenAddress(
+    userTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
       underlyingMint,
       wallet.publicKey
-    );
+    )).address;
 
-    // Create user share account
+    // Create user share account (share mint is a Token-2022 mint)
     userShareAccount = await getAssociatedTokenAddress(
       shareMint,
-      wallet.publicKey
+      wallet.publicKey,
+      false,
+      TOKEN_2022_PROGRAM_ID
     );
   });
 
@@ -116,7 +371,10 @@ describe("solana-yield-farming-vaults", () => {
         null, // Use default management fee
         null, // Use default performance fee
         null, // No leverage (1x)
-        minDeposit
+        minDeposit,
+        "YV-TEST-AutoCompound",
+        "yvTEST",
+        "https://example.com/yv-test.json"
       )
       .accounts({
         vault: vault,
@@ -125,6 +383,9 @@ describe("solana-yield-farming-vaults", () => {
         shareMint: shareMint,
         vaultTokenAccount: vaultTokenAccount,
         authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
@@ -137,6 +398,61 @@ describe("solana-yield-farming-vaults", () => {
     expect(vaultAccount.totalAssets.toString()).to.equal("0");
     expect(vaultAccount.totalShares.toString()).to.equal("0");
     expect(vaultAccount.paused).to.be.false;
+    expect(vaultAccount.shareMint.toString()).to.equal(shareMint.toString());
+    expect(vaultAccount.vaultTokenAccount.toString()).to.equal(vaultTokenAccount.toString());
+
+    // The vault PDA is the share mint authority, with decimals matching the underlying
+    const shareMintInfo = await getMint(
+      provider.connection,
+      shareMint,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(shareMintInfo.mintAuthority.toString()).to.equal(vault.toString());
+    expect(shareMintInfo.decimals).to.equal(9);
+    // Share transfers call back into the program to checkpoint emission rewards
+    expect(getTransferHook(shareMintInfo).programId.toString()).to.equal(program.programId.toString());
+
+    // Share token metadata lives on the mint itself
+    const metadata = await getTokenMetadata(provider.connection, shareMint);
+    expect(metadata.name).to.equal("YV-TEST-AutoCompound");
+    expect(metadata.symbol).to.equal("yvTEST");
+    expect(metadata.updateAuthority.toString()).to.equal(vault.toString());
+  });
+
+  it("Updates share token metadata", async () => {
+    await program.methods
+      .updateShareMetadata(null, null, "https://example.com/yv-test-v2.json")
+      .accounts({
+        vault: vault,
+        shareMint: shareMint,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const metadata = await getTokenMetadata(provider.connection, shareMint);
+    expect(metadata.name).to.equal("YV-TEST-AutoCompound");
+    expect(metadata.uri).to.equal("https://example.com/yv-test-v2.json");
+
+    // Symbol above the length cap should fail
+    try {
+      await program.methods
+        .updateShareMetadata(null, "yvTOOLONGSYMBOL", null)
+        .accounts({
+          vault: vault,
+          shareMint: shareMint,
+          authority: wallet.publicKey,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      expect.fail("Oversized symbol should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidShareMetadata");
+    }
   });
 
   it("Deposits into vault", async () => {
@@ -158,10 +474,12 @@ describe("solana-yield-farming-vaults", () => {
         vault: vault,
         vaultTokenAccount: vaultTokenAccount,
         userTokenAccount: userTokenAccount,
+        underlyingMint: underlyingMint,
         shareMint: shareMint,
         userShareAccount: userShareAccount,
         user: wallet.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
       })
       .rpc();
 
@@ -182,10 +500,12 @@ describe("solana-yield-farming-vaults", () => {
         vault: vault,
         vaultTokenAccount: vaultTokenAccount,
         userTokenAccount: userTokenAccount,
+        underlyingMint: underlyingMint,
         shareMint: shareMint,
         userShareAccount: userShareAccount,
         user: wallet.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
       })
       .rpc();
 
@@ -224,10 +544,12 @@ describe("solana-yield-farming-vaults", () => {
           vault: vault,
           vaultTokenAccount: vaultTokenAccount,
           userTokenAccount: userTokenAccount,
+          underlyingMint: underlyingMint,
           shareMint: shareMint,
           userShareAccount: userShareAccount,
           user: wallet.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_2022_PROGRAM_ID,
         })
         .rpc();
       
@@ -250,4 +572,1952 @@ describe("solana-yield-farming-vaults", () => {
     vaultAccount = await program.account.vault.fetch(vault);
     expect(vaultAccount.paused).to.be.false;
   });
+
+  it("Migrates only vaults in the original layout", async () => {
+    // A vault created by the current program is already in the current layout
+    try {
+      await program.methods
+        .migrateVault(new anchor.BN(0))
+        .accounts({
+          authority: wallet.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Migrating a current vault should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("NotLegacyVault");
+    }
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    expect(vaultAccount.vaultId.toString()).to.equal("0");
+  });
+
+  it("Sets a capped harvester bounty", async () => {
+    await program.methods
+      .updateVaultParams({ ...noParamChanges, harvestBountyBps: 100 }) // 1% bounty
+      .accounts({
+        vault: vault,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(vault);
+    expect(vaultAccount.harvestBountyBps).to.equal(100);
+
+    // Bounty above the 5% cap should fail
+    try {
+      await program.methods
+        .updateVaultParams({ ...noParamChanges, harvestBountyBps: 501 })
+        .accounts({
+          vault: vault,
+          authority: wallet.publicKey,
+        })
+        .rpc();
+
+      expect.fail("Bounty above cap should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidFeeConfig");
+    }
+  });
+
+  it("Reports a bounded strategy loss", async () => {
+    const vaultAccountBefore = await program.account.vault.fetch(vault);
+    const loss = vaultAccountBefore.totalAssets.div(new anchor.BN(100)); // 1%
+
+    await program.methods
+      .reportLoss(loss, true)
+      .accounts({
+        vault: vault,
+        vaultTokenAccount: vaultTokenAccount,
+        underlyingMint: underlyingMint,
+        reserveTokenAccount: null, // No reserve: loss goes to locked profit, then share price
+        reporter: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const vaultAccountAfter = await program.account.vault.fetch(vault);
+    expect(vaultAccountAfter.totalAssets.toString()).to.equal(
+      vaultAccountBefore.totalAssets.sub(loss).toString()
+    );
+    expect(vaultAccountAfter.depositsPaused).to.be.false; // Below 5% threshold
+
+    // Loss above the 10% per-report bound should fail
+    try {
+      await program.methods
+        .reportLoss(vaultAccountAfter.totalAssets.div(new anchor.BN(5)), true)
+        .accounts({
+          vault: vault,
+          vaultTokenAccount: vaultTokenAccount,
+          underlyingMint: underlyingMint,
+          reserveTokenAccount: null,
+          reporter: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect.fail("Loss above bound should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidLossReport");
+    }
+  });
+
+  it("Opens a user position", async () => {
+    const [userPositionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_position"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeUserPosition()
+      .accounts({
+        vault: vault,
+        userPosition: userPositionPda,
+        user: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lendingProgram: null, // LP vault: no lending obligation
+        lendingMarket: null,
+        obligation: null,
+      })
+      .rpc();
+
+    const position = await program.account.userPosition.fetch(userPositionPda);
+    expect(position.user.toString()).to.equal(wallet.publicKey.toString());
+    expect(position.leverageBps).to.equal(10000);
+    expect(position.obligation.toString()).to.equal(anchor.web3.PublicKey.default.toString());
+  });
+
+  it("Tracks cost basis and PnL on the user position", async () => {
+    const [userPositionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_position"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const depositAmount = new anchor.BN(100000000); // 0.1 tokens
+
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      userTokenAccount,
+      wallet.publicKey,
+      depositAmount.toNumber()
+    );
+
+    await program.methods
+      .deposit(depositAmount)
+      .accounts({
+        vault: vault,
+        vaultTokenAccount: vaultTokenAccount,
+        userTokenAccount: userTokenAccount,
+        underlyingMint: underlyingMint,
+        shareMint: shareMint,
+        userShareAccount: userShareAccount,
+        user: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        userPosition: userPositionPda,
+      })
+      .rpc();
+
+    const position = await program.account.userPosition.fetch(userPositionPda);
+    expect(position.totalDeposited.toString()).to.equal(depositAmount.toString());
+    expect(position.shares.gt(new anchor.BN(0))).to.be.true;
+
+    const pnl = await program.methods
+      .getPositionPnl()
+      .accounts({
+        vault: vault,
+        userPosition: userPositionPda,
+        userShareAccount: userShareAccount,
+        userLock: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("user_lock"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        underlyingMint: null,
+        tokenBMint: null,
+      })
+      .view();
+
+    expect(pnl.shares.toString()).to.equal(position.shares.toString());
+    expect(pnl.totalDeposited.toString()).to.equal(depositAmount.toString());
+    expect(pnl.realizedPnl.toString()).to.equal("0");
+    // Freshly bought shares are worth their cost, up to rounding
+    expect(pnl.unrealizedPnl.abs().lte(new anchor.BN(1))).to.be.true;
+  });
+
+  it("Locks shares for a tier and penalizes early unlock", async () => {
+    const [lockEscrow] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("lock_escrow"), vault.toBuffer()],
+      program.programId
+    );
+    const [userLockPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_lock"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeLocks(2000, 1000) // 20% harvest boost, 10% early-unlock penalty
+      .accounts({
+        vault: vault,
+        lockEscrow: lockEscrow,
+        shareMint: shareMint,
+        authority: wallet.publicKey,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeUserLock()
+      .accounts({
+        vault: vault,
+        userLock: userLockPda,
+        user: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const lockAccounts = {
+      vault: vault,
+      userLock: userLockPda,
+      lockEscrow: lockEscrow,
+      shareMint: shareMint,
+      userShareAccount: userShareAccount,
+      user: wallet.publicKey,
+      shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+
+    try {
+      await program.methods
+        .lockShares(new anchor.BN(1000), 3)
+        .accounts(lockAccounts)
+        .rpc();
+
+      expect.fail("Unknown lock tier should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidLockTier");
+    }
+
+    const lockAmount = new anchor.BN(10000);
+    await program.methods
+      .lockShares(lockAmount, 1) // 90 days
+      .accounts(lockAccounts)
+      .rpc();
+
+    const userLock = await program.account.userLock.fetch(userLockPda);
+    expect(userLock.shares.toString()).to.equal(lockAmount.toString());
+    expect(userLock.weight.toString()).to.equal("15000"); // 1.5x
+    expect(userLock.tier).to.equal(1);
+    const escrow = await getAccount(provider.connection, lockEscrow, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(escrow.amount.toString()).to.equal(lockAmount.toString());
+
+    const vaultBefore = await program.account.vault.fetch(vault);
+    expect(vaultBefore.totalLockWeight.toString()).to.equal("15000");
+
+    await program.methods
+      .unlockShares()
+      .accounts({
+        vault: vault,
+        userLock: userLockPda,
+        lockEscrow: lockEscrow,
+        shareMint: shareMint,
+        userShareAccount: userShareAccount,
+        user: wallet.publicKey,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    // No other lockers share the 10% penalty, so it is burned for all holders
+    const vaultAfter = await program.account.vault.fetch(vault);
+    expect(vaultBefore.totalShares.sub(vaultAfter.totalShares).toString()).to.equal("1000");
+    expect(vaultAfter.totalLockedShares.toString()).to.equal("0");
+    const unlocked = await program.account.userLock.fetch(userLockPda);
+    expect(unlocked.shares.toString()).to.equal("0");
+  });
+
+  it("Emits secondary rewards to opted-in share holders", async () => {
+    const [rewardDistributor] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_distributor"), vault.toBuffer()],
+      program.programId
+    );
+    const [userRewardsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_rewards"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const [userLockPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_lock"), vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const rewardMint = await createMint(
+      provider.connection,
+      wallet.payer,
+      wallet.publicKey,
+      null,
+      6
+    );
+    const [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("reward_vault"), vault.toBuffer(), rewardMint.toBuffer()],
+      program.programId
+    );
+    const authorityRewardAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      rewardMint,
+      wallet.publicKey
+    )).address;
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      rewardMint,
+      authorityRewardAccount,
+      wallet.publicKey,
+      1_000_000_000
+    );
+
+    await program.methods
+      .initializeRewardDistributor()
+      .accounts({
+        vault: vault,
+        rewardDistributor: rewardDistributor,
+        authority: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .addRewardMint()
+      .accounts({
+        vault: vault,
+        rewardDistributor: rewardDistributor,
+        rewardMint: rewardMint,
+        rewardVault: rewardVault,
+        authority: wallet.publicKey,
+        rewardTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const fundAccounts = {
+      vault: vault,
+      rewardDistributor: rewardDistributor,
+      rewardMint: rewardMint,
+      rewardVault: rewardVault,
+      authorityRewardAccount: authorityRewardAccount,
+      authority: wallet.publicKey,
+      rewardTokenProgram: TOKEN_PROGRAM_ID,
+    };
+    const endTs = new anchor.BN(Math.floor(Date.now() / 1000) + 600);
+
+    try {
+      // 1,000,000 per second for 10 minutes is far more than the funding
+      await program.methods
+        .fundRewardEmission(new anchor.BN(1_000_000), new anchor.BN(1_000_000), new anchor.BN(0), endTs)
+        .accounts(fundAccounts)
+        .rpc();
+
+      expect.fail("Underfunded emission should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InsufficientRewardFunding");
+    }
+
+    await program.methods
+      .fundRewardEmission(new anchor.BN(1_000_000), new anchor.BN(1_000), new anchor.BN(0), endTs)
+      .accounts(fundAccounts)
+      .rpc();
+
+    await program.methods
+      .initializeUserRewards()
+      .accounts({
+        vault: vault,
+        rewardDistributor: rewardDistributor,
+        userRewards: userRewardsPda,
+        userShareAccount: userShareAccount,
+        userLock: userLockPda,
+        user: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const optedIn = await program.account.userRewards.fetch(userRewardsPda);
+    const shareBalance = await getAccount(provider.connection, userShareAccount, undefined, TOKEN_2022_PROGRAM_ID);
+    expect(optedIn.shares.toString()).to.equal(shareBalance.amount.toString());
+    const distributor = await program.account.rewardDistributor.fetch(rewardDistributor);
+    expect(distributor.totalShares.toString()).to.equal(optedIn.shares.toString());
+    // Nobody was opted in between funding and opting in, so the schedule was paused
+    expect(distributor.emissions[0].endTs.gte(endTs)).to.be.true;
+
+    // A share transfer moves reward shares out of the sender through the hook
+    const recipient = anchor.web3.Keypair.generate();
+    const recipientShareAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      shareMint,
+      recipient.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    await transferCheckedWithTransferHook(
+      provider.connection,
+      wallet.payer,
+      userShareAccount,
+      shareMint,
+      recipientShareAccount,
+      wallet.publicKey,
+      BigInt(1000),
+      9,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const afterTransfer = await program.account.userRewards.fetch(userRewardsPda);
+    expect(optedIn.shares.sub(afterTransfer.shares).toString()).to.equal("1000");
+
+    // Let a few seconds of emissions accrue, then claim them
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    // The test wallet both funds the emission and holds the shares
+    const userRewardAccount = authorityRewardAccount;
+    const before = await getAccount(provider.connection, userRewardAccount);
+    await program.methods
+      .claimRewards()
+      .accounts({
+        vault: vault,
+        rewardDistributor: rewardDistributor,
+        userRewards: userRewardsPda,
+        rewardMint: rewardMint,
+        rewardVault: rewardVault,
+        userRewardAccount: userRewardAccount,
+        user: wallet.publicKey,
+        rewardTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, userRewardAccount);
+    expect(after.amount > before.amount).to.be.true;
+    const claimed = await program.account.userRewards.fetch(userRewardsPda);
+    expect(claimed.pendingRewards[0].toString()).to.equal("0");
+  });
+
+  it("Deposits and withdraws native SOL", async () => {
+    const vaultId = (await program.account.globalState.fetch(globalStatePda)).vaultCount;
+    const [solVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [solShareMint] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), solVault.toBuffer()],
+      program.programId
+    );
+    const solVaultTokenAccount = await getAssociatedTokenAddress(NATIVE_MINT, solVault, true);
+
+    await program.methods
+      .initializeVault(0, null, null, null, new anchor.BN(1000000), "YV-SOL-AutoCompound", "yvSOL", "")
+      .accounts({
+        vault: solVault,
+        globalState: globalStatePda,
+        underlyingMint: NATIVE_MINT,
+        shareMint: solShareMint,
+        vaultTokenAccount: solVaultTokenAccount,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    const solShareAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      solShareMint,
+      wallet.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [tempWsolAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("wsol"), solVault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const solAccounts = {
+      vault: solVault,
+      vaultTokenAccount: solVaultTokenAccount,
+      tempWsolAccount: tempWsolAccount,
+      underlyingMint: NATIVE_MINT,
+      shareMint: solShareMint,
+      userShareAccount: solShareAccount.address,
+      user: wallet.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    const lamports = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL);
+    await program.methods.depositSol(lamports).accounts(solAccounts).rpc();
+
+    let vaultAccount = await program.account.vault.fetch(solVault);
+    expect(vaultAccount.totalAssets.toString()).to.equal(lamports.toString());
+    // Temporary WSOL account is closed again
+    expect(await provider.connection.getAccountInfo(tempWsolAccount)).to.be.null;
+
+    const balanceBefore = await provider.connection.getBalance(wallet.publicKey);
+    await program.methods.withdrawSol(vaultAccount.totalShares).accounts(solAccounts).rpc();
+
+    vaultAccount = await program.account.vault.fetch(solVault);
+    expect(vaultAccount.totalAssets.toString()).to.equal("0");
+    expect(await provider.connection.getAccountInfo(tempWsolAccount)).to.be.null;
+    const balanceAfter = await provider.connection.getBalance(wallet.publicKey);
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(0.99 * anchor.web3.LAMPORTS_PER_SOL);
+
+    // Vaults with another underlying reject the SOL path
+    try {
+      await program.methods
+        .depositSol(lamports)
+        .accounts({ ...solAccounts, vault: vault })
+        .rpc();
+
+      expect.fail("deposit_sol should have failed for a non-SOL vault");
+    } catch (err) {
+      expect(err.toString()).to.include("NotNativeSolVault");
+    }
+  });
+
+  it("Marks a liquid staking vault to its stake pool rate", async () => {
+    // jitoSOL stake pool and mint, loaded from tests/fixtures (see Anchor.toml)
+    const jitoStakePool = new anchor.web3.PublicKey("Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb");
+    const jitoSolMint = new anchor.web3.PublicKey("J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn");
+
+    const vaultId = (await program.account.globalState.fetch(globalStatePda)).vaultCount;
+    const [lstVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), vaultId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [lstShareMint] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), lstVault.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeVault(4, null, null, null, new anchor.BN(1000000), "YV-JITOSOL-Staking", "yvJitoSOL", "")
+      .accounts({
+        vault: lstVault,
+        globalState: globalStatePda,
+        underlyingMint: jitoSolMint,
+        shareMint: lstShareMint,
+        vaultTokenAccount: await getAssociatedTokenAddress(jitoSolMint, lstVault, true),
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Accounts not owned by the stake pool program are rejected
+    try {
+      await program.methods
+        .initializeStakePool()
+        .accounts({ vault: lstVault, stakePool: globalStatePda, authority: wallet.publicKey })
+        .rpc();
+
+      expect.fail("Non stake pool account should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidStakePool");
+    }
+
+    await program.methods
+      .initializeStakePool()
+      .accounts({ vault: lstVault, stakePool: jitoStakePool, authority: wallet.publicKey })
+      .rpc();
+
+    const vaultAccount = await program.account.vault.fetch(lstVault);
+    expect(vaultAccount.strategyConfig.stakePool.toString()).to.equal(jitoStakePool.toString());
+    // The fixture pool holds 1.17 SOL per jitoSOL
+    expect(vaultAccount.lstExchangeRate.toString()).to.equal("1170000000");
+  });
+
+  it("Borrows and repays a leveraged position through the lending adapter", async () => {
+    const leveraged = await createTestVault(1, 30000, "YV-TEST-Leveraged", "yvLEV");
+
+    const [lendingMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), underlyingMint.toBuffer()],
+      mockLending.programId
+    );
+    const [liquiditySupply] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("supply"), lendingMarket.toBuffer()],
+      mockLending.programId
+    );
+
+    await mockLending.methods
+      .initMarket(9000) // 90% max LTV
+      .accounts({
+        market: lendingMarket,
+        mint: underlyingMint,
+        liquiditySupply: liquiditySupply,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Lenders' liquidity the vault borrows from
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      liquiditySupply,
+      wallet.publicKey,
+      10000000000
+    );
+
+    await updateStrategy(leveraged.vault, {
+      lendingProtocol: mockLending.programId,
+      lendingMarket: lendingMarket,
+    });
+
+    const [userPositionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_position"), leveraged.vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const [obligation] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("obligation"),
+        lendingMarket.toBuffer(),
+        leveraged.vault.toBuffer(),
+        userPositionPda.toBuffer(),
+      ],
+      mockLending.programId
+    );
+
+    await program.methods
+      .initializeUserPosition()
+      .accounts({
+        vault: leveraged.vault,
+        userPosition: userPositionPda,
+        user: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lendingProgram: mockLending.programId,
+        lendingMarket: lendingMarket,
+        obligation: obligation,
+      })
+      .rpc();
+
+    let position = await program.account.userPosition.fetch(userPositionPda);
+    expect(position.obligation.toString()).to.equal(obligation.toString());
+
+    // Idle vault liquidity repays debt when the position deleverages
+    const idle = new anchor.BN(1000000000);
+    await depositInto(leveraged, idle);
+
+    const leverageAccounts = {
+      vault: leveraged.vault,
+      userPosition: userPositionPda,
+      vaultTokenAccount: leveraged.vaultTokenAccount,
+      userTokenAccount: userTokenAccount,
+      user: wallet.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      underlyingMint: underlyingMint,
+      lendingProgram: mockLending.programId,
+      lendingMarket: lendingMarket,
+      obligation: obligation,
+      liquiditySupply: liquiditySupply,
+      ...sameAssetDebtAccounts,
+    };
+    const collateral = new anchor.BN(400000000);
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      userTokenAccount,
+      wallet.publicKey,
+      collateral.toNumber()
+    );
+
+    // Recorded debt always mirrors the obligation on the lending market
+    const expectPosition = async (deposited: number, borrowed: number) => {
+      const lendingObligation = await mockLending.account.obligation.fetch(obligation);
+      expect(lendingObligation.depositedAmount.toString()).to.equal(deposited.toString());
+      expect(lendingObligation.borrowedAmount.toString()).to.equal(borrowed.toString());
+
+      position = await program.account.userPosition.fetch(userPositionPda);
+      expect(position.debt.toString()).to.equal(lendingObligation.borrowedAmount.toString());
+
+      const vaultAccount = await program.account.vault.fetch(leveraged.vault);
+      expect(vaultAccount.totalDebt.toString()).to.equal(position.debt.toString());
+      expect(vaultAccount.totalCollateral.toString()).to.equal(position.collateral.toString());
+      // Position collateral mints no shares and stays out of total assets
+      expect(vaultAccount.totalAssets.toString()).to.equal(idle.toString());
+    };
+
+    // 1.5x: supply the collateral, borrow half of it and loop the loan back in
+    await program.methods
+      .adjustLeverage(15000, collateral, new anchor.BN(0))
+      .accounts(leverageAccounts)
+      .rpc();
+    await expectPosition(600000000, 200000000);
+    expect(position.collateral.toString()).to.equal(collateral.toString());
+    expect(position.leverageBps).to.equal(15000);
+
+    // 2x borrows the difference
+    await program.methods
+      .adjustLeverage(20000, new anchor.BN(0), new anchor.BN(0))
+      .accounts(leverageAccounts)
+      .rpc();
+    await expectPosition(800000000, 400000000);
+
+    // Back to 1.5x repays from idle liquidity and unwinds the same amount of collateral
+    await program.methods
+      .adjustLeverage(15000, new anchor.BN(0), new anchor.BN(0))
+      .accounts(leverageAccounts)
+      .rpc();
+    await expectPosition(600000000, 200000000);
+
+    // Closing out repays the rest and returns the collateral to the user
+    const userBefore = await getAccount(provider.connection, userTokenAccount);
+    await program.methods
+      .adjustLeverage(10000, new anchor.BN(0), collateral)
+      .accounts(leverageAccounts)
+      .rpc();
+    await expectPosition(0, 0);
+    expect(position.collateral.toString()).to.equal("0");
+
+    const userAfter = await getAccount(provider.connection, userTokenAccount);
+    expect((userAfter.amount - userBefore.amount).toString()).to.equal(collateral.toString());
+    const vaultTokens = await getAccount(provider.connection, leveraged.vaultTokenAccount);
+    expect(vaultTokens.amount.toString()).to.equal(idle.toString());
+  });
+
+  it("Manages a CLMM range and collects its fees on harvest", async () => {
+    const lp = await createTestVault(0, null, "YV-TEST-CLMM", "yvCLMM");
+
+    const [clmmPool] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("clmm_pool"), underlyingMint.toBuffer()],
+      mockClmm.programId
+    );
+    const [poolTokenVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), clmmPool.toBuffer()],
+      mockClmm.programId
+    );
+    const [clmmPosition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("clmm_position"), clmmPool.toBuffer(), lp.vault.toBuffer()],
+      mockClmm.programId
+    );
+
+    await mockClmm.methods
+      .initPool(0)
+      .accounts({
+        pool: clmmPool,
+        mint: underlyingMint,
+        tokenVault: poolTokenVault,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await updateStrategy(lp.vault, {
+      clmmProgram: mockClmm.programId,
+      poolAddress: clmmPool,
+    });
+
+    const deposited = new anchor.BN(1000000000);
+    await depositInto(lp, deposited);
+
+    const liquidity = new anchor.BN(600000000);
+    await program.methods
+      .openClmmPosition(-100, 100, liquidity)
+      .accounts({
+        vault: lp.vault,
+        vaultTokenAccount: lp.vaultTokenAccount,
+        clmmProgram: mockClmm.programId,
+        pool: clmmPool,
+        position: clmmPosition,
+        poolTokenVault: poolTokenVault,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    let position = await mockClmm.account.clmmPosition.fetch(clmmPosition);
+    expect(position.owner.toString()).to.equal(lp.vault.toString());
+    expect(position.liquidity.toString()).to.equal(liquidity.toString());
+    let vaultAccount = await program.account.vault.fetch(lp.vault);
+    expect(vaultAccount.strategyConfig.clmmPosition.toString()).to.equal(clmmPosition.toString());
+    // Deployed liquidity stays part of total assets
+    expect(vaultAccount.totalAssets.toString()).to.equal(deposited.toString());
+    let vaultTokens = await getAccount(provider.connection, lp.vaultTokenAccount);
+    expect(vaultTokens.amount.toString()).to.equal(deposited.sub(liquidity).toString());
+
+    // The open position's range survives strategy updates, and its pool cannot change
+    await updateStrategy(lp.vault, { tickLower: -5000, tickUpper: 5000 });
+    vaultAccount = await program.account.vault.fetch(lp.vault);
+    expect(vaultAccount.strategyConfig.tickLower).to.equal(-100);
+    expect(vaultAccount.strategyConfig.tickUpper).to.equal(100);
+    try {
+      await updateStrategy(lp.vault, { poolAddress: anchor.web3.Keypair.generate().publicKey });
+
+      expect.fail("Changing the pool of an open position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("VenueInUse");
+    }
+
+    const resetAccounts = {
+      vault: lp.vault,
+      vaultTokenAccount: lp.vaultTokenAccount,
+      clmmProgram: mockClmm.programId,
+      pool: clmmPool,
+      position: clmmPosition,
+      poolTokenVault: poolTokenVault,
+      authority: wallet.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // In-range positions are left alone
+    try {
+      await program.methods.resetRange().accounts(resetAccounts).rpc();
+
+      expect.fail("Reset of an in-range position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("PriceInRange");
+    }
+
+    await mockClmm.methods
+      .setTick(500)
+      .accounts({ pool: clmmPool, authority: wallet.publicKey })
+      .rpc();
+
+    // Only the vault authority may recenter the range
+    const keeper = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .resetRange()
+        .accounts({ ...resetAccounts, authority: keeper.publicKey })
+        .signers([keeper])
+        .rpc();
+
+      expect.fail("Reset by a non-authority should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("Unauthorized");
+    }
+
+    await program.methods.resetRange().accounts(resetAccounts).rpc();
+
+    // Same width, centered on the new tick, with the liquidity redeployed
+    position = await mockClmm.account.clmmPosition.fetch(clmmPosition);
+    expect(position.tickLower).to.equal(400);
+    expect(position.tickUpper).to.equal(600);
+    expect(position.liquidity.toString()).to.equal(liquidity.toString());
+    vaultAccount = await program.account.vault.fetch(lp.vault);
+    expect(vaultAccount.strategyConfig.tickLower).to.equal(400);
+    expect(vaultAccount.strategyConfig.tickUpper).to.equal(600);
+
+    // Trading fees accrue to the position and are collected by the next harvest
+    const fees = new anchor.BN(50000000);
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      underlyingMint,
+      userTokenAccount,
+      wallet.publicKey,
+      fees.toNumber()
+    );
+    await mockClmm.methods
+      .accrueFees(fees)
+      .accounts({
+        pool: clmmPool,
+        position: clmmPosition,
+        tokenVault: poolTokenVault,
+        funderTokenAccount: userTokenAccount,
+        funder: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    await program.methods
+      .updateVaultParams({ ...noParamChanges, harvestCooldown: new anchor.BN(0) })
+      .accounts({
+        vault: lp.vault,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+
+    const assetsBefore = vaultAccount.totalAssets;
+    const tokensBefore = (await getAccount(provider.connection, lp.vaultTokenAccount)).amount;
+
+    await program.methods
+      .harvest(new anchor.BN(0)) // No underlying-denominated rewards: CLMM fees only
+      .accounts({
+        vault: lp.vault,
+        globalState: globalStatePda,
+        vaultTokenAccount: lp.vaultTokenAccount,
+        underlyingMint: underlyingMint,
+        rewardsTokenAccount: userTokenAccount,
+        rewardsAuthority: wallet.publicKey,
+        harvester: wallet.publicKey,
+        harvesterTokenAccount: userTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        reserveTokenAccount: null,
+        swapProgram: null,
+        clmmProgram: mockClmm.programId,
+        clmmPool: clmmPool,
+        clmmPosition: clmmPosition,
+        clmmPoolTokenVault: poolTokenVault,
+        stakePool: null,
+      })
+      .rpc();
+
+    position = await mockClmm.account.clmmPosition.fetch(clmmPosition);
+    expect(position.feesOwed.toString()).to.equal("0");
+    const tokensAfter = (await getAccount(provider.connection, lp.vaultTokenAccount)).amount;
+    expect((tokensAfter - tokensBefore).toString()).to.equal(fees.toString());
+    vaultAccount = await program.account.vault.fetch(lp.vault);
+    expect(vaultAccount.totalAssets.gt(assetsBefore)).to.be.true;
+  });
+
+  it("Hedges the LP leg of a delta-neutral vault with a perp short", async () => {
+    const hedged = await createTestVault(3, null, "YV-TEST-DeltaNeutral", "yvDN");
+
+    // Pool created by the CLMM test; its current tick is 500
+    const [clmmPool] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("clmm_pool"), underlyingMint.toBuffer()],
+      mockClmm.programId
+    );
+    const [poolTokenVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault"), clmmPool.toBuffer()],
+      mockClmm.programId
+    );
+    const [clmmPosition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("clmm_position"), clmmPool.toBuffer(), hedged.vault.toBuffer()],
+      mockClmm.programId
+    );
+    const [perpMarket] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("perp_market"), underlyingMint.toBuffer()],
+      mockPerp.programId
+    );
+    const [marginVault] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("margin_vault"), perpMarket.toBuffer()],
+      mockPerp.programId
+    );
+    const [perpPosition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("perp_position"), perpMarket.toBuffer(), hedged.vault.toBuffer()],
+      mockPerp.programId
+    );
+
+    // Mark price at the (stub) oracle price of 1.0
+    await mockPerp.methods
+      .initMarket(50000, new anchor.BN(100000000)) // 5x max leverage
+      .accounts({
+        market: perpMarket,
+        mint: underlyingMint,
+        marginVault: marginVault,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await updateStrategy(hedged.vault, {
+      clmmProgram: mockClmm.programId,
+      poolAddress: clmmPool,
+      perpProgram: mockPerp.programId,
+      perpMarket: perpMarket,
+      oraclePriceFeed: anchor.web3.Keypair.generate().publicKey,
+      maxSwapSlippageBps: 100, // 1%
+      rehedgeBandBps: 500, // 5%
+    });
+
+    await depositInto(hedged, new anchor.BN(1000000000));
+
+    // The LP leg is the spot exposure the hedge offsets
+    const liquidityAccounts = {
+      vault: hedged.vault,
+      vaultTokenAccount: hedged.vaultTokenAccount,
+      clmmProgram: mockClmm.programId,
+      pool: clmmPool,
+      position: clmmPosition,
+      poolTokenVault: poolTokenVault,
+      authority: wallet.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+    await program.methods
+      .openClmmPosition(400, 600, new anchor.BN(600000000))
+      .accounts({ ...liquidityAccounts, systemProgram: anchor.web3.SystemProgram.programId })
+      .rpc();
+
+    await program.methods
+      .initializeHedge(new anchor.BN(200000000))
+      .accounts({
+        vault: hedged.vault,
+        vaultTokenAccount: hedged.vaultTokenAccount,
+        perpProgram: mockPerp.programId,
+        perpMarket: perpMarket,
+        perpPosition: perpPosition,
+        marginVault: marginVault,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Idle cash and posted margin are not part of the exposure
+    let vaultAccount = await program.account.vault.fetch(hedged.vault);
+    expect(vaultAccount.strategyConfig.hedgePosition.toString()).to.equal(perpPosition.toString());
+    expect(vaultAccount.netDelta.toString()).to.equal("600000000");
+    let position = await mockPerp.account.perpPosition.fetch(perpPosition);
+    expect(position.margin.toString()).to.equal("200000000");
+
+    // Any keeper can rehedge
+    const keeper = anchor.web3.Keypair.generate();
+    const rehedgeAccounts = {
+      vault: hedged.vault,
+      vaultTokenAccount: hedged.vaultTokenAccount,
+      perpProgram: mockPerp.programId,
+      perpMarket: perpMarket,
+      perpPosition: perpPosition,
+      keeper: keeper.publicKey,
+    };
+    const rehedge = () => program.methods.rehedge().accounts(rehedgeAccounts).signers([keeper]).rpc();
+
+    await rehedge();
+    position = await mockPerp.account.perpPosition.fetch(perpPosition);
+    expect(position.baseSize.toString()).to.equal("-600000000");
+    vaultAccount = await program.account.vault.fetch(hedged.vault);
+    expect(vaultAccount.hedgeSize.toString()).to.equal("-600000000");
+    expect(vaultAccount.netDelta.toString()).to.equal("0");
+
+    // A fully hedged vault has nothing to trade
+    try {
+      await rehedge();
+
+      expect.fail("Rehedge of a hedged vault should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("RehedgeNotNeeded");
+    }
+
+    // 20 more tokens in the LP leg is a 20/620 ≈ 3.2% drift, inside the 5% band
+    await program.methods
+      .adjustClmmLiquidity(new anchor.BN(20000000), true)
+      .accounts(liquidityAccounts)
+      .rpc();
+    try {
+      await rehedge();
+
+      expect.fail("Rehedge inside the band should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("RehedgeNotNeeded");
+    }
+
+    // 40 more takes it to 60/660 ≈ 9.1%, outside the band
+    await program.methods
+      .adjustClmmLiquidity(new anchor.BN(40000000), true)
+      .accounts(liquidityAccounts)
+      .rpc();
+
+    // The short is limited to the oracle price less 1% slippage
+    await mockPerp.methods
+      .setMarkPrice(new anchor.BN(90000000))
+      .accounts({ market: perpMarket, authority: wallet.publicKey })
+      .rpc();
+    try {
+      await rehedge();
+
+      expect.fail("Rehedge below the limit price should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("PriceLimitExceeded");
+    }
+
+    await mockPerp.methods
+      .setMarkPrice(new anchor.BN(99500000))
+      .accounts({ market: perpMarket, authority: wallet.publicKey })
+      .rpc();
+    await rehedge();
+
+    position = await mockPerp.account.perpPosition.fetch(perpPosition);
+    expect(position.baseSize.toString()).to.equal("-660000000");
+    vaultAccount = await program.account.vault.fetch(hedged.vault);
+    expect(vaultAccount.hedgeSize.toString()).to.equal(position.baseSize.toString());
+    expect(vaultAccount.netDelta.toString()).to.equal("0");
+  });
+
+  it("Swaps reward tokens into the underlying on harvest", async () => {
+    const compounding = await createTestVault(2, null, "YV-TEST-RewardSwap", "yvSWAP");
+    await depositInto(compounding, new anchor.BN(1000000000));
+
+    // Two reward mints (same decimals as the underlying), each with a harvest
+    // rewards account and a pool into the underlying
+    const rewards: {
+      mint: anchor.web3.PublicKey;
+      harvestRewardsAccount: anchor.web3.PublicKey;
+      pool: anchor.web3.PublicKey;
+      poolSource: anchor.web3.PublicKey;
+      poolDestination: anchor.web3.PublicKey;
+      amount: number;
+    }[] = [];
+    for (const [rate, amount] of [[10000, 100000000], [9800, 200000000]]) {
+      const mint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 9);
+      const [harvestRewardsAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("harvest_rewards"), compounding.vault.toBuffer(), mint.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .initializeHarvestRewardsAccount()
+        .accounts({
+          vault: compounding.vault,
+          rewardMint: mint,
+          harvestRewardsAccount: harvestRewardsAccount,
+          authority: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await mintTo(provider.connection, wallet.payer, mint, harvestRewardsAccount, wallet.publicKey, amount);
+
+      const [pool] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("swap_pool"), mint.toBuffer(), underlyingMint.toBuffer()],
+        mockSwap.programId
+      );
+      const [poolSource] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_source"), pool.toBuffer()],
+        mockSwap.programId
+      );
+      const [poolDestination] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("pool_destination"), pool.toBuffer()],
+        mockSwap.programId
+      );
+      await mockSwap.methods
+        .initPool(rate)
+        .accounts({
+          pool: pool,
+          mintIn: mint,
+          mintOut: underlyingMint,
+          poolSource: poolSource,
+          poolDestination: poolDestination,
+          authority: wallet.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      await mintTo(provider.connection, wallet.payer, underlyingMint, poolDestination, wallet.publicKey, 1000000000);
+
+      rewards.push({ mint, harvestRewardsAccount, pool, poolSource, poolDestination, amount });
+    }
+
+    // One group of 5 remaining accounts per reward mint
+    const swapGroup = (reward: typeof rewards[number]) => [
+      { pubkey: reward.harvestRewardsAccount, isWritable: true, isSigner: false },
+      { pubkey: reward.mint, isWritable: false, isSigner: false },
+      { pubkey: reward.pool, isWritable: true, isSigner: false },
+      { pubkey: reward.poolSource, isWritable: true, isSigner: false },
+      { pubkey: reward.poolDestination, isWritable: true, isSigner: false },
+    ];
+    const harvest = (remainingAccounts: anchor.web3.AccountMeta[]) =>
+      program.methods
+        .harvest(new anchor.BN(0)) // No underlying-denominated rewards: swapped rewards only
+        .accounts({
+          vault: compounding.vault,
+          globalState: globalStatePda,
+          vaultTokenAccount: compounding.vaultTokenAccount,
+          underlyingMint: underlyingMint,
+          rewardsTokenAccount: userTokenAccount,
+          rewardsAuthority: wallet.publicKey,
+          harvester: wallet.publicKey,
+          harvesterTokenAccount: userTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          reserveTokenAccount: null,
+          swapProgram: mockSwap.programId,
+          clmmProgram: null,
+          clmmPool: null,
+          clmmPosition: null,
+          clmmPoolTokenVault: null,
+          stakePool: null,
+        })
+        .remainingAccounts(remainingAccounts)
+        .rpc();
+
+    await program.methods
+      .updateVaultParams({ ...noParamChanges, harvestCooldown: new anchor.BN(0) })
+      .accounts({
+        vault: compounding.vault,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+
+    // Only the first reward mint is registered so far
+    const emptyRewardMint = {
+      mint: anchor.web3.PublicKey.default,
+      oraclePriceFeed: anchor.web3.PublicKey.default,
+    };
+    const rewardMintConfig = (reward: typeof rewards[number]) => ({
+      mint: reward.mint,
+      oraclePriceFeed: anchor.web3.Keypair.generate().publicKey,
+    });
+    await updateStrategy(compounding.vault, {
+      swapProgram: mockSwap.programId,
+      oraclePriceFeed: anchor.web3.Keypair.generate().publicKey,
+      maxSwapSlippageBps: 100, // 1%
+      rewardMints: [rewardMintConfig(rewards[0]), emptyRewardMint, emptyRewardMint, emptyRewardMint],
+    });
+
+    try {
+      await harvest([...swapGroup(rewards[0]), ...swapGroup(rewards[1])]);
+
+      expect.fail("Swapping an unregistered reward mint should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidMint");
+    }
+
+    // Any other vault-owned account of the mint is not a swap source
+    const otherVaultAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      rewards[0].mint,
+      compounding.vault,
+      true
+    )).address;
+    await mintTo(provider.connection, wallet.payer, rewards[0].mint, otherVaultAccount, wallet.publicKey, 50000000);
+    try {
+      await harvest([
+        { pubkey: otherVaultAccount, isWritable: true, isSigner: false },
+        ...swapGroup(rewards[0]).slice(1),
+      ]);
+
+      expect.fail("Swapping out of a non-harvest-rewards account should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidRewardAccounts");
+    }
+
+    try {
+      await harvest(swapGroup(rewards[0]).slice(0, 4));
+
+      expect.fail("Harvest with an incomplete swap group should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidRewardAccounts");
+    }
+
+    await updateStrategy(compounding.vault, {
+      rewardMints: [
+        rewardMintConfig(rewards[0]),
+        rewardMintConfig(rewards[1]),
+        emptyRewardMint,
+        emptyRewardMint,
+      ],
+    });
+
+    // The second pool pays 2% under the oracle price, past the 1% slippage bound
+    try {
+      await harvest([...swapGroup(rewards[0]), ...swapGroup(rewards[1])]);
+
+      expect.fail("Swap below the oracle-bounded minimum out should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("SlippageExceeded");
+    }
+
+    await mockSwap.methods
+      .setRate(9950)
+      .accounts({ pool: rewards[1].pool, authority: wallet.publicKey })
+      .rpc();
+
+    const assetsBefore = (await program.account.vault.fetch(compounding.vault)).totalAssets;
+    const tokensBefore = (await getAccount(provider.connection, compounding.vaultTokenAccount)).amount;
+
+    await harvest([...swapGroup(rewards[0]), ...swapGroup(rewards[1])]);
+
+    // 100 at par plus 200 at 0.995
+    const tokensAfter = (await getAccount(provider.connection, compounding.vaultTokenAccount)).amount;
+    expect((tokensAfter - tokensBefore).toString()).to.equal("299000000");
+    for (const reward of rewards) {
+      const rewardAccount = await getAccount(provider.connection, reward.harvestRewardsAccount);
+      expect(rewardAccount.amount.toString()).to.equal("0");
+      const poolSource = await getAccount(provider.connection, reward.poolSource);
+      expect(poolSource.amount.toString()).to.equal(reward.amount.toString());
+    }
+    const vaultAccount = await program.account.vault.fetch(compounding.vault);
+    expect(vaultAccount.totalAssets.gt(assetsBefore)).to.be.true;
+  });
+
+  it("Liquidates an unhealthy position within the close factor", async () => {
+    const idle = new anchor.BN(1000000000);
+    const leveraged = await createLeveragedVault("YV-TEST-Liquidation", "yvLIQ", idle);
+    const { userPosition, obligation } = await openLeveragedPosition(
+      leveraged,
+      wallet.payer,
+      userTokenAccount,
+      new anchor.BN(400000000),
+      20000 // 2x: 800 supplied, 400 borrowed
+    );
+
+    await updateStrategy(leveraged.vault, {
+      closeFactorBps: 5000, // 50% of the debt per call
+      liquidationBonusBps: 10800, // 8% bonus
+      liquidationProtocolFeeBps: 2500, // 25% of the bonus to the treasury
+    });
+
+    const liquidator = await createActor(1000000000);
+    const liquidate = (debtToRepay: number) =>
+      program.methods
+        .liquidate(new anchor.BN(debtToRepay))
+        .accounts({
+          vault: leveraged.vault,
+          userPosition: userPosition,
+          vaultTokenAccount: leveraged.vaultTokenAccount,
+          liquidatorTokenAccount: liquidator.tokenAccount,
+          liquidatorDebtTokenAccount: null,
+          globalState: globalStatePda,
+          reserveTokenAccount: null,
+          treasuryTokenAccount: userTokenAccount, // Treasury is the test wallet
+          liquidator: liquidator.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          underlyingMint: underlyingMint,
+          lendingProgram: mockLending.programId,
+          lendingMarket: leveraged.lendingMarket,
+          obligation: obligation,
+          liquiditySupply: leveraged.liquiditySupply,
+          ...sameAssetDebtAccounts,
+        })
+        .signers([liquidator.keypair])
+        .rpc();
+
+    // Health (400 + 400) * 80% / 400 = 1.6x is above the 1.1x threshold
+    try {
+      await liquidate(100000000);
+
+      expect.fail("Liquidating a healthy position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("LiquidationNotNeeded");
+    }
+
+    // A 50% collateral factor takes health to 1.0x
+    await updateStrategy(leveraged.vault, { collateralFactorBps: 5000 });
+
+    try {
+      await liquidate(200000001);
+
+      expect.fail("Repaying more than the close factor should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("CloseFactorExceeded");
+    }
+
+    const liquidatorBefore = (await getAccount(provider.connection, liquidator.tokenAccount)).amount;
+    const treasuryBefore = (await getAccount(provider.connection, userTokenAccount)).amount;
+
+    // Repaying 200 seizes 216: the 16 bonus splits 12 to the liquidator and 4 to the treasury
+    await liquidate(200000000);
+
+    const liquidatorAfter = (await getAccount(provider.connection, liquidator.tokenAccount)).amount;
+    expect((liquidatorAfter - liquidatorBefore).toString()).to.equal("12000000");
+    const treasuryAfter = (await getAccount(provider.connection, userTokenAccount)).amount;
+    expect((treasuryAfter - treasuryBefore).toString()).to.equal("4000000");
+
+    const lendingObligation = await mockLending.account.obligation.fetch(obligation);
+    expect(lendingObligation.depositedAmount.toString()).to.equal("584000000");
+    expect(lendingObligation.borrowedAmount.toString()).to.equal("200000000");
+
+    // Equity is 400 + 200 repaid - 216 seized
+    const position = await program.account.userPosition.fetch(userPosition);
+    expect(position.collateral.toString()).to.equal("384000000");
+    expect(position.debt.toString()).to.equal("200000000");
+    expect(position.leverageBps).to.equal(15208);
+
+    const vaultAccount = await program.account.vault.fetch(leveraged.vault);
+    expect(vaultAccount.totalCollateral.toString()).to.equal("384000000");
+    expect(vaultAccount.totalDebt.toString()).to.equal("200000000");
+    expect(vaultAccount.currentLeverageBps).to.equal(15208);
+    // Seized collateral was the position's own; share holders are untouched
+    expect(vaultAccount.totalAssets.toString()).to.equal(idle.toString());
+    const vaultTokens = await getAccount(provider.connection, leveraged.vaultTokenAccount);
+    expect(vaultTokens.amount.toString()).to.equal(idle.toString());
+
+    // Health (384 + 200) * 50% / 200 = 1.46x is back above the threshold
+    try {
+      await liquidate(50000000);
+
+      expect.fail("Liquidating a recovered position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("LiquidationNotNeeded");
+    }
+  });
+
+  it("Runs a Dutch liquidation auction", async () => {
+    const leveraged = await createLeveragedVault("YV-TEST-Auction", "yvAUC", new anchor.BN(1000000000));
+    const { userPosition, obligation } = await openLeveragedPosition(
+      leveraged,
+      wallet.payer,
+      userTokenAccount,
+      new anchor.BN(400000000),
+      20000 // 2x: 800 supplied, 400 borrowed
+    );
+
+    await updateStrategy(leveraged.vault, {
+      auctionMaxBonusBps: 11000, // Bonus grows from 0% to 10%
+      auctionDuration: new anchor.BN(600),
+      collateralFactorBps: 5000, // Health 1.0x, below the 1.1x threshold
+    });
+
+    const liquidator = await createActor(1000000000);
+    const liquidate = (debtToRepay: number) =>
+      program.methods
+        .liquidate(new anchor.BN(debtToRepay))
+        .accounts({
+          vault: leveraged.vault,
+          userPosition: userPosition,
+          vaultTokenAccount: leveraged.vaultTokenAccount,
+          liquidatorTokenAccount: liquidator.tokenAccount,
+          liquidatorDebtTokenAccount: null,
+          globalState: globalStatePda,
+          reserveTokenAccount: null,
+          treasuryTokenAccount: userTokenAccount,
+          liquidator: liquidator.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          underlyingMint: underlyingMint,
+          lendingProgram: mockLending.programId,
+          lendingMarket: leveraged.lendingMarket,
+          obligation: obligation,
+          liquiditySupply: leveraged.liquiditySupply,
+          ...sameAssetDebtAccounts,
+        })
+        .signers([liquidator.keypair])
+        .rpc();
+    const auctionAccounts = {
+      vault: leveraged.vault,
+      userPosition: userPosition,
+      underlyingMint: underlyingMint,
+      debtMint: null,
+    };
+    const startAuction = () =>
+      program.methods
+        .startLiquidationAuction()
+        .accounts({ ...auctionAccounts, starter: liquidator.keypair.publicKey })
+        .signers([liquidator.keypair])
+        .rpc();
+    const cancelAuction = () =>
+      program.methods
+        .cancelLiquidationAuction()
+        .accounts(auctionAccounts)
+        .rpc();
+
+    // In auction mode liquidations fill only against a running auction
+    try {
+      await liquidate(100000000);
+
+      expect.fail("Liquidation without an auction should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AuctionNotStarted");
+    }
+
+    await startAuction();
+    let position = await program.account.userPosition.fetch(userPosition);
+    expect(position.liquidationAuctionStart.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await startAuction();
+
+      expect.fail("Restarting a running auction should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AuctionAlreadyStarted");
+    }
+
+    // The auction can only be cancelled once the position is healthy again
+    try {
+      await cancelAuction();
+
+      expect.fail("Cancelling the auction of an unhealthy position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("LiquidationNeeded");
+    }
+
+    await updateStrategy(leveraged.vault, { collateralFactorBps: 8000 });
+    await cancelAuction();
+    position = await program.account.userPosition.fetch(userPosition);
+    expect(position.liquidationAuctionStart.toNumber()).to.equal(0);
+
+    try {
+      await cancelAuction();
+
+      expect.fail("Cancelling without an auction should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AuctionNotStarted");
+    }
+
+    // A fresh auction fills near a 0% bonus, well short of the 10% maximum
+    await updateStrategy(leveraged.vault, { collateralFactorBps: 5000 });
+    await startAuction();
+    await liquidate(100000000);
+
+    const lendingObligation = await mockLending.account.obligation.fetch(obligation);
+    const seized = 800000000 - lendingObligation.depositedAmount.toNumber();
+    expect(seized).to.be.at.least(100000000);
+    expect(seized).to.be.lessThan(102000000);
+    expect(lendingObligation.borrowedAmount.toString()).to.equal("300000000");
+
+    // The first fill ends the auction
+    position = await program.account.userPosition.fetch(userPosition);
+    expect(position.liquidationAuctionStart.toNumber()).to.equal(0);
+    expect(position.collateral.toNumber()).to.equal(400000000 + 100000000 - seized);
+
+    // Health is back to about (400 + 300) * 50% / 300 = 1.17x; 40% takes it under
+    await updateStrategy(leveraged.vault, { collateralFactorBps: 4000 });
+    try {
+      await liquidate(100000000);
+
+      expect.fail("Liquidation after the auction was filled should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AuctionNotStarted");
+    }
+
+    // An auction left unfilled past its duration expires and can be restarted
+    await updateStrategy(leveraged.vault, { auctionDuration: new anchor.BN(1) });
+    await startAuction();
+    const expiredStart = (await program.account.userPosition.fetch(userPosition)).liquidationAuctionStart;
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    try {
+      await liquidate(100000000);
+
+      expect.fail("Filling an expired auction should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AuctionExpired");
+    }
+
+    await startAuction();
+    position = await program.account.userPosition.fetch(userPosition);
+    expect(position.liquidationAuctionStart.gt(expiredStart)).to.be.true;
+  });
+
+  it("Writes off bad debt when a liquidation exhausts the collateral", async () => {
+    const idle = new anchor.BN(1000000000);
+    const leveraged = await createLeveragedVault("YV-TEST-BadDebt", "yvBAD", idle);
+    const { userPosition, obligation } = await openLeveragedPosition(
+      leveraged,
+      wallet.payer,
+      userTokenAccount,
+      new anchor.BN(400000000),
+      20000 // 2x: 800 supplied, 400 borrowed
+    );
+
+    // 700 of the 800 supplied is lost on the lending side, leaving 100 against 400 of debt
+    await mockLending.methods
+      .writeDownCollateral(new anchor.BN(700000000))
+      .accounts({
+        market: leveraged.lendingMarket,
+        obligation: obligation,
+        authority: wallet.publicKey,
+      })
+      .rpc();
+    await updateStrategy(leveraged.vault, { collateralFactorBps: 5000 });
+
+    const liquidator = await createActor(1000000000);
+    const liquidatorBefore = (await getAccount(provider.connection, liquidator.tokenAccount)).amount;
+
+    // Repaying 200 would seize 210 at the default 5% bonus, so the seizure is capped
+    // at the 100 left: the liquidator repays 100 / 1.05 and the rest is bad debt
+    await program.methods
+      .liquidate(new anchor.BN(200000000))
+      .accounts({
+        vault: leveraged.vault,
+        userPosition: userPosition,
+        vaultTokenAccount: leveraged.vaultTokenAccount,
+        liquidatorTokenAccount: liquidator.tokenAccount,
+        liquidatorDebtTokenAccount: null,
+        globalState: globalStatePda,
+        reserveTokenAccount: null, // No reserve: the bad debt is socialized
+        treasuryTokenAccount: userTokenAccount,
+        liquidator: liquidator.keypair.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        underlyingMint: underlyingMint,
+        lendingProgram: mockLending.programId,
+        lendingMarket: leveraged.lendingMarket,
+        obligation: obligation,
+        liquiditySupply: leveraged.liquiditySupply,
+        ...sameAssetDebtAccounts,
+      })
+      .signers([liquidator.keypair])
+      .rpc();
+
+    const repaid = 95238095;
+    const badDebt = 400000000 - repaid;
+
+    const liquidatorAfter = (await getAccount(provider.connection, liquidator.tokenAccount)).amount;
+    expect((liquidatorAfter - liquidatorBefore).toString()).to.equal((100000000 - repaid).toString());
+
+    // The vault repaid the residual, closing out the obligation
+    const lendingObligation = await mockLending.account.obligation.fetch(obligation);
+    expect(lendingObligation.depositedAmount.toString()).to.equal("0");
+    expect(lendingObligation.borrowedAmount.toString()).to.equal("0");
+
+    const position = await program.account.userPosition.fetch(userPosition);
+    expect(position.collateral.toString()).to.equal("0");
+    expect(position.debt.toString()).to.equal("0");
+    expect(position.leverageBps).to.equal(10000);
+
+    // Share holders absorb the write-off
+    const vaultAccount = await program.account.vault.fetch(leveraged.vault);
+    expect(vaultAccount.totalAssets.toString()).to.equal(idle.sub(new anchor.BN(badDebt)).toString());
+    expect(vaultAccount.totalCollateral.toString()).to.equal("0");
+    expect(vaultAccount.totalDebt.toString()).to.equal("0");
+    const vaultTokens = await getAccount(provider.connection, leveraged.vaultTokenAccount);
+    expect(vaultTokens.amount.toString()).to.equal(vaultAccount.totalAssets.toString());
+  });
+
+  it("Auto-deleverages an at-risk position and tracks vault-wide leverage", async () => {
+    const idle = new anchor.BN(1000000000);
+    const leveraged = await createLeveragedVault("YV-TEST-AutoDeleverage", "yvADL", idle);
+    const { userPosition, obligation } = await openLeveragedPosition(
+      leveraged,
+      wallet.payer,
+      userTokenAccount,
+      new anchor.BN(400000000),
+      20000 // 2x: 800 supplied, 400 borrowed
+    );
+
+    // A second borrower at 1.5x: 300 supplied, 100 borrowed
+    const borrower = await createActor(0);
+    const second = await openLeveragedPosition(
+      leveraged,
+      borrower.keypair,
+      borrower.tokenAccount,
+      new anchor.BN(200000000),
+      15000
+    );
+
+    // Vault-wide leverage is derived from both positions: (600 + 500) / 600
+    let vaultAccount = await program.account.vault.fetch(leveraged.vault);
+    expect(vaultAccount.totalCollateral.toString()).to.equal("600000000");
+    expect(vaultAccount.totalDebt.toString()).to.equal("500000000");
+    expect(vaultAccount.currentLeverageBps).to.equal(18333);
+
+    const keeper = await createActor(0);
+    const autoDeleverage = (target: { userPosition: anchor.web3.PublicKey; obligation: anchor.web3.PublicKey }) =>
+      program.methods
+        .autoDeleverage()
+        .accounts({
+          vault: leveraged.vault,
+          userPosition: target.userPosition,
+          vaultTokenAccount: leveraged.vaultTokenAccount,
+          keeperTokenAccount: keeper.tokenAccount,
+          keeper: keeper.keypair.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          underlyingMint: underlyingMint,
+          lendingProgram: mockLending.programId,
+          lendingMarket: leveraged.lendingMarket,
+          obligation: target.obligation,
+          liquiditySupply: leveraged.liquiditySupply,
+          ...sameAssetDebtAccounts,
+        })
+        .signers([keeper.keypair])
+        .rpc();
+    const first = { userPosition, obligation };
+
+    try {
+      await autoDeleverage(first);
+
+      expect.fail("Auto-deleverage without a threshold should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("AutoDeleverageNotEnabled");
+    }
+
+    await updateStrategy(leveraged.vault, {
+      deleverageThresholdBps: 17000, // Soft threshold at 1.7x health
+      deleverageTargetLeverageBps: 15000,
+      keeperFeeBps: 50, // 0.5% of the repaid debt
+    });
+
+    // Health (200 + 100) * 80% / 100 = 2.4x
+    try {
+      await autoDeleverage(second);
+
+      expect.fail("Auto-deleverage of a safe position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("DeleverageNotNeeded");
+    }
+
+    // Health (400 + 400) * 80% / 400 = 1.6x: repay down to 1.5x (200 of debt)
+    // and pay the keeper 0.5% of the 200 repaid from the position's collateral
+    const vaultTokensBefore = (await getAccount(provider.connection, leveraged.vaultTokenAccount)).amount;
+    await autoDeleverage(first);
+
+    const keeperTokens = await getAccount(provider.connection, keeper.tokenAccount);
+    expect(keeperTokens.amount.toString()).to.equal("1000000");
+
+    const lendingObligation = await mockLending.account.obligation.fetch(obligation);
+    expect(lendingObligation.depositedAmount.toString()).to.equal("599000000");
+    expect(lendingObligation.borrowedAmount.toString()).to.equal("200000000");
+
+    const position = await program.account.userPosition.fetch(userPosition);
+    expect(position.collateral.toString()).to.equal("399000000");
+    expect(position.debt.toString()).to.equal("200000000");
+    expect(position.leverageBps).to.equal(15012);
+
+    vaultAccount = await program.account.vault.fetch(leveraged.vault);
+    expect(vaultAccount.totalCollateral.toString()).to.equal("599000000");
+    expect(vaultAccount.totalDebt.toString()).to.equal("300000000");
+    expect(vaultAccount.currentLeverageBps).to.equal(15008);
+    // The repayment is unwound from looped collateral; share holders are untouched
+    expect(vaultAccount.totalAssets.toString()).to.equal(idle.toString());
+    const vaultTokensAfter = (await getAccount(provider.connection, leveraged.vaultTokenAccount)).amount;
+    expect(vaultTokensAfter.toString()).to.equal(vaultTokensBefore.toString());
+
+    try {
+      await autoDeleverage(first);
+
+      expect.fail("Auto-deleverage of a deleveraged position should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("DeleverageNotNeeded");
+    }
+  });
+
+  it("Deposits and withdraws both legs of a dual-asset vault", async () => {
+    const pair = await createTestVault(0, null, "YV-TEST-Pair", "yvPAIR");
+    const [pairPosition] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("user_position"), pair.vault.toBuffer(), wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeUserPosition()
+      .accounts({
+        vault: pair.vault,
+        userPosition: pairPosition,
+        user: wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        lendingProgram: null,
+        lendingMarket: null,
+        obligation: null,
+      })
+      .rpc();
+
+    const tokenBMint = await createMint(provider.connection, wallet.payer, wallet.publicKey, null, 9);
+    const vaultTokenBAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      tokenBMint,
+      pair.vault,
+      true
+    )).address;
+    const userTokenBAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      tokenBMint,
+      wallet.publicKey
+    )).address;
+    await mintTo(provider.connection, wallet.payer, tokenBMint, userTokenBAccount, wallet.publicKey, 800000000);
+    await mintTo(provider.connection, wallet.payer, underlyingMint, userTokenAccount, wallet.publicKey, 600000000);
+
+    // Both legs are priced at the (stub) oracle price of 1.0
+    const initializeTokenB = () =>
+      program.methods
+        .initializeTokenB()
+        .accounts({
+          vault: pair.vault,
+          tokenBMint: tokenBMint,
+          vaultTokenBAccount: vaultTokenBAccount,
+          tokenBOracle: anchor.web3.Keypair.generate().publicKey,
+          authority: wallet.publicKey,
+        })
+        .rpc();
+    await initializeTokenB();
+    await updateStrategy(pair.vault, {
+      oraclePriceFeed: anchor.web3.Keypair.generate().publicKey,
+    });
+
+    // Pool swapping token B into token A
+    const [swapPool] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("swap_pool"), tokenBMint.toBuffer(), underlyingMint.toBuffer()],
+      mockSwap.programId
+    );
+    const [swapPoolSource] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_source"), swapPool.toBuffer()],
+      mockSwap.programId
+    );
+    const [swapPoolDestination] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_destination"), swapPool.toBuffer()],
+      mockSwap.programId
+    );
+    await mockSwap.methods
+      .initPool(9800)
+      .accounts({
+        pool: swapPool,
+        mintIn: tokenBMint,
+        mintOut: underlyingMint,
+        poolSource: swapPoolSource,
+        poolDestination: swapPoolDestination,
+        authority: wallet.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await mintTo(provider.connection, wallet.payer, underlyingMint, swapPoolDestination, wallet.publicKey, 1000000000);
+
+    const pairAccounts = {
+      vault: pair.vault,
+      vaultTokenAccount: pair.vaultTokenAccount,
+      vaultTokenBAccount: vaultTokenBAccount,
+      userTokenAccount: userTokenAccount,
+      userTokenBAccount: userTokenBAccount,
+      underlyingMint: underlyingMint,
+      tokenBMint: tokenBMint,
+      shareMint: pair.shareMint,
+      userShareAccount: pair.userShareAccount,
+      user: wallet.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      shareTokenProgram: TOKEN_2022_PROGRAM_ID,
+    };
+    const noSwapAccounts = {
+      swapProgram: null,
+      swapPool: null,
+      swapPoolSource: null,
+      swapPoolDestination: null,
+    };
+    const depositPair = (amountA: number, amountB: number, swapToRatio: boolean) =>
+      program.methods
+        .depositPair(new anchor.BN(amountA), new anchor.BN(amountB), swapToRatio)
+        .accounts({
+          ...pairAccounts,
+          ...(swapToRatio
+            ? {
+                swapProgram: mockSwap.programId,
+                swapPool: swapPool,
+                swapPoolSource: swapPoolSource,
+                swapPoolDestination: swapPoolDestination,
+              }
+            : noSwapAccounts),
+        })
+        .rpc();
+    const expectTotals = async (assetsA: number, assetsB: number, shares: number) => {
+      const vaultAccount = await program.account.vault.fetch(pair.vault);
+      expect(vaultAccount.totalAssets.toString()).to.equal(assetsA.toString());
+      expect(vaultAccount.totalAssetsB.toString()).to.equal(assetsB.toString());
+      expect(vaultAccount.totalShares.toString()).to.equal(shares.toString());
+
+      const vaultA = await getAccount(provider.connection, pair.vaultTokenAccount);
+      expect(vaultA.amount.toString()).to.equal(assetsA.toString());
+      const vaultB = await getAccount(provider.connection, vaultTokenBAccount);
+      expect(vaultB.amount.toString()).to.equal(assetsB.toString());
+      const userShares = await getAccount(
+        provider.connection,
+        pair.userShareAccount,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(userShares.amount.toString()).to.equal(shares.toString());
+    };
+
+    // An empty vault takes both amounts as given; shares are the combined value
+    await depositPair(300000000, 100000000, false);
+    await expectTotals(300000000, 100000000, 400000000);
+
+    // Token B can no longer be set once shares exist
+    try {
+      await initializeTokenB();
+
+      expect.fail("Initializing token B on a funded vault should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("InvalidStrategy");
+    }
+
+    // Later deposits are trimmed to the vault's 3:1 ratio: only 100 of the 300 B is pulled
+    let userB = (await getAccount(provider.connection, userTokenBAccount)).amount;
+    await depositPair(300000000, 300000000, false);
+    await expectTotals(600000000, 200000000, 800000000);
+    expect((userB - (await getAccount(provider.connection, userTokenBAccount)).amount).toString())
+      .to.equal("100000000");
+
+    // Single-sided B with a swap: 300 of the 400 B is swapped into A to keep the ratio
+    await updateStrategy(pair.vault, {
+      swapProgram: mockSwap.programId,
+      maxSwapSlippageBps: 100, // 1%
+    });
+
+    // The pool pays 2% under the oracle price, past the 1% slippage bound
+    try {
+      await depositPair(0, 400000000, true);
+
+      expect.fail("Swap below the oracle-bounded minimum out should have failed");
+    } catch (err) {
+      expect(err.toString()).to.include("SlippageExceeded");
+    }
+
+    await mockSwap.methods
+      .setRate(10000)
+      .accounts({ pool: swapPool, authority: wallet.publicKey })
+      .rpc();
+
+    userB = (await getAccount(provider.connection, userTokenBAccount)).amount;
+    await depositPair(0, 400000000, true);
+    await expectTotals(900000000, 300000000, 1200000000);
+    expect((userB - (await getAccount(provider.connection, userTokenBAccount)).amount).toString())
+      .to.equal("400000000");
+    const poolSource = await getAccount(provider.connection, swapPoolSource);
+    expect(poolSource.amount.toString()).to.equal("300000000");
+
+    // Withdrawing half the shares pays out half of each leg
+    const userA = (await getAccount(provider.connection, userTokenAccount)).amount;
+    userB = (await getAccount(provider.connection, userTokenBAccount)).amount;
+
+    await program.methods
+      .withdrawPair(new anchor.BN(600000000))
+      .accounts(pairAccounts)
+      .rpc();
+
+    await expectTotals(450000000, 150000000, 600000000);
+    expect(((await getAccount(provider.connection, userTokenAccount)).amount - userA).toString())
+      .to.equal("450000000");
+    expect(((await getAccount(provider.connection, userTokenBAccount)).amount - userB).toString())
+      .to.equal("150000000");
+
+    // The position is costed in token A units, token B included
+    const position = await program.account.userPosition.fetch(pairPosition);
+    expect(position.totalDeposited.toString()).to.equal("1200000000");
+    expect(position.totalWithdrawn.toString()).to.equal("600000000");
+    expect(position.shares.toString()).to.equal("600000000");
+    expect(position.realizedPnl.abs().lte(new anchor.BN(1))).to.be.true;
+
+    // Shares transferred away take their cost basis with them
+    const recipient = anchor.web3.Keypair.generate();
+    const recipientShareAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      pair.shareMint,
+      recipient.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    )).address;
+    await transferCheckedWithTransferHook(
+      provider.connection,
+      wallet.payer,
+      pair.userShareAccount,
+      pair.shareMint,
+      recipientShareAccount,
+      wallet.publicKey,
+      BigInt(200000000),
+      9,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const pnl = await program.methods
+      .getPositionPnl()
+      .accounts({
+        vault: pair.vault,
+        userPosition: pairPosition,
+        userShareAccount: pair.userShareAccount,
+        userLock: anchor.web3.PublicKey.findProgramAddressSync(
+          [Buffer.from("user_lock"), pair.vault.toBuffer(), wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        underlyingMint: underlyingMint,
+        tokenBMint: tokenBMint,
+      })
+      .view();
+    expect(pnl.shares.toString()).to.equal("400000000");
+    expect(pnl.currentValue.toString()).to.equal("400000000");
+    expect(pnl.unrealizedPnl.abs().lte(new anchor.BN(1))).to.be.true;
+  });
 });